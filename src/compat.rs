@@ -0,0 +1,190 @@
+// Copyright 2022 Redglyph
+//
+// Logarithm trait compatible with the `core` integer methods
+
+use crate::{Log10, Log2};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that provides logarithms with the same names and signatures as the `core` methods of the
+/// integer primitives: [`ilog`](ILog::ilog), [`ilog2`](ILog::ilog2), [`ilog10`](ILog::ilog10) and their
+/// checked versions, which return a `u32`.
+///
+/// It can replace the `core` methods by changing an import, for example in generic code or with
+/// references. Note that the inherent `core` methods take precedence over the trait methods when
+/// they're called on a primitive with the method syntax, which gives the same result.
+///
+/// Like in `core`, the unchecked methods panic if the logarithm is undefined for the parameter value.
+pub trait ILog: Sized {
+    /// Returns the logarithm of the number with respect to an arbitrary base, rounded down.
+    ///
+    /// # Panics
+    /// This function panics if `self` is less than or equal to zero, or if `base` is less than 2.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::ILog;
+    /// assert_eq!(ILog::ilog(&125_u32, &5), 3);
+    /// ```
+    fn ilog(self, base: Self) -> u32;
+
+    /// Returns the base 2 logarithm of the number, rounded down.
+    ///
+    /// # Panics
+    /// This function panics if `self` is less than or equal to zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::ILog;
+    /// fn log2(x: impl ILog) -> u32 {
+    ///     x.ilog2()
+    /// }
+    /// assert_eq!(log2(64_i64), 6);
+    /// ```
+    fn ilog2(self) -> u32;
+
+    /// Returns the base 10 logarithm of the number, rounded down.
+    ///
+    /// # Panics
+    /// This function panics if `self` is less than or equal to zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::ILog;
+    /// let value = Box::new(99_u16);
+    /// assert_eq!(value.ilog10(), 1);
+    /// ```
+    fn ilog10(self) -> u32;
+
+    /// Returns the logarithm of the number with respect to an arbitrary base, rounded down,
+    /// or `None` if the number is negative or zero, or if the base is not at least 2.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::ILog;
+    /// assert_eq!(ILog::checked_ilog(&124_u32, &5), Some(2));
+    /// assert_eq!(ILog::checked_ilog(&124_u32, &1), None);
+    /// ```
+    fn checked_ilog(self, base: Self) -> Option<u32>;
+
+    /// Returns the base 2 logarithm of the number, rounded down, or `None` if the number is negative or zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::ILog;
+    /// assert_eq!(ILog::checked_ilog2(&63_i32), Some(5));
+    /// assert_eq!(ILog::checked_ilog2(&-63_i32), None);
+    /// ```
+    fn checked_ilog2(self) -> Option<u32>;
+
+    /// Returns the base 10 logarithm of the number, rounded down, or `None` if the number is negative or zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::ILog;
+    /// assert_eq!(ILog::checked_ilog10(&100_u8), Some(2));
+    /// assert_eq!(ILog::checked_ilog10(&0_u8), None);
+    /// ```
+    fn checked_ilog10(self) -> Option<u32>;
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `ILog` trait to references
+macro_rules! forward_ref_ilog {
+    ($imp:ident for $( $t:ty ),+) => {$(
+        impl $imp for $t {
+            #[inline]
+            fn ilog(self, base: Self) -> u32 {
+                $imp::ilog(*self, *base)
+            }
+            #[inline]
+            fn ilog2(self) -> u32 {
+                $imp::ilog2(*self)
+            }
+            #[inline]
+            fn ilog10(self) -> u32 {
+                $imp::ilog10(*self)
+            }
+            #[inline]
+            fn checked_ilog(self, base: Self) -> Option<u32> {
+                $imp::checked_ilog(*self, *base)
+            }
+            #[inline]
+            fn checked_ilog2(self) -> Option<u32> {
+                $imp::checked_ilog2(*self)
+            }
+            #[inline]
+            fn checked_ilog10(self) -> Option<u32> {
+                $imp::checked_ilog10(*self)
+            }
+        }
+    )+}
+}
+
+/// Implements `ILog` trait for integer type, using the `Log2` and `Log10` implementations
+macro_rules! impl_ilog {
+    ($($SelfT: ty),+) => {$(
+        // The logarithms of the integer primitives are always smaller than `u32::MAX`.
+        #[allow(clippy::cast_possible_truncation)]
+        impl ILog for $SelfT {
+            #[inline]
+            fn ilog(self, base: Self) -> u32 {
+                assert!(base >= 2, "base of integer logarithm must be at least 2");
+                match ILog::checked_ilog(self, base) {
+                    Some(log) => log,
+                    None => panic!("argument of integer logarithm must be positive"),
+                }
+            }
+
+            #[inline]
+            fn ilog2(self) -> u32 {
+                match ILog::checked_ilog2(self) {
+                    Some(log) => log,
+                    None => panic!("argument of integer logarithm must be positive"),
+                }
+            }
+
+            #[inline]
+            fn ilog10(self) -> u32 {
+                match ILog::checked_ilog10(self) {
+                    Some(log) => log,
+                    None => panic!("argument of integer logarithm must be positive"),
+                }
+            }
+
+            #[inline]
+            fn checked_ilog(self, base: Self) -> Option<u32> {
+                match base {
+                    2 => ILog::checked_ilog2(self),
+                    10 => ILog::checked_ilog10(self),
+                    _ if self <= 0 || base < 2 => None,
+                    _ => {
+                        let mut log = 0;
+                        let mut value = self;
+                        while value >= base {
+                            value /= base;
+                            log += 1;
+                        }
+                        Some(log)
+                    }
+                }
+            }
+
+            #[inline]
+            fn checked_ilog2(self) -> Option<u32> {
+                Log2::checked_log2(self).map(|log| log as u32)
+            }
+
+            #[inline]
+            fn checked_ilog10(self) -> Option<u32> {
+                Log10::checked_log10(self).map(|log| log as u32)
+            }
+        }
+
+        forward_ref_ilog!(ILog for &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_ilog! { u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize }
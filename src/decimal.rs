@@ -0,0 +1,165 @@
+// Copyright 2022 Redglyph
+//
+// Decimal formatting of integers into fixed buffers, without `core::fmt`
+
+use crate::Log10;
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that writes the decimal representation of integers into byte buffers, without `core::fmt`.
+///
+/// The length of the representation is computed first with [`log10`](Log10::log10), then the digits
+/// are written from right to left in a single pass.
+///
+/// # Examples
+/// ```
+/// use ilog::DecimalFormat;
+///
+/// let mut buffer = [0_u8; 8];
+/// let len = (-1234_i32).write_decimal(&mut buffer);
+/// assert_eq!(&buffer[..len], b"-1234");
+///
+/// let (array, len) = u64::MAX.to_decimal_array();
+/// assert_eq!(&array[..len], b"18446744073709551615");
+/// ```
+pub trait DecimalFormat: Log10 {
+    /// Array that can hold the decimal representation of any value of the type, which is
+    /// `[u8; Self::MAX_DECIMAL_LEN]`.
+    type Array;
+
+    /// Returns the length of the decimal representation of the integer, including the minus sign
+    /// for negative values.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::DecimalFormat;
+    /// assert_eq!(0_u8.decimal_len(), 1);
+    /// assert_eq!(1000_u32.decimal_len(), 4);
+    /// assert_eq!((-1000_i32).decimal_len(), 5);
+    /// ```
+    fn decimal_len(self) -> usize;
+
+    /// Writes the decimal representation of the integer at the beginning of `buffer`, and returns
+    /// its length.
+    ///
+    /// # Panics
+    /// This function panics if `buffer` is shorter than the [`decimal_len`](Self::decimal_len) of
+    /// the integer. A buffer of [`MAX_DECIMAL_LEN`](Log10::MAX_DECIMAL_LEN) bytes is always large enough.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::{DecimalFormat, Log10};
+    /// let mut buffer = [0_u8; i16::MAX_DECIMAL_LEN];
+    /// let len = i16::MIN.write_decimal(&mut buffer);
+    /// assert_eq!(&buffer[..len], b"-32768");
+    /// ```
+    fn write_decimal(self, buffer: &mut [u8]) -> usize;
+
+    /// Returns an array containing the decimal representation of the integer at its beginning,
+    /// and the length of the representation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::DecimalFormat;
+    /// let (array, len) = 255_u8.to_decimal_array();
+    /// assert_eq!(array, *b"255");
+    /// assert_eq!(len, 3);
+    /// ```
+    fn to_decimal_array(self) -> (Self::Array, usize);
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `DecimalFormat` trait to references
+macro_rules! forward_ref_decimal {
+    ($SelfT: ty; $( $t:ty ),+) => {$(
+        impl DecimalFormat for $t {
+            type Array = <$SelfT as DecimalFormat>::Array;
+
+            #[inline]
+            fn decimal_len(self) -> usize {
+                DecimalFormat::decimal_len(*self)
+            }
+            #[inline]
+            fn write_decimal(self, buffer: &mut [u8]) -> usize {
+                DecimalFormat::write_decimal(*self, buffer)
+            }
+            #[inline]
+            fn to_decimal_array(self) -> (Self::Array, usize) {
+                DecimalFormat::to_decimal_array(*self)
+            }
+        }
+    )+}
+}
+
+/// Implements `DecimalFormat` trait for unsigned integer type
+macro_rules! impl_unsigned_decimal {
+    ($($SelfT: ty),+) => {$(
+        impl DecimalFormat for $SelfT {
+            type Array = [u8; <$SelfT as Log10>::MAX_DECIMAL_LEN];
+
+            #[inline]
+            fn decimal_len(self) -> usize {
+                self.checked_log10().map_or(1, |log| log + 1)
+            }
+
+            #[inline]
+            fn write_decimal(self, buffer: &mut [u8]) -> usize {
+                let len = self.decimal_len();
+                let mut value = self;
+                for digit in buffer[..len].iter_mut().rev() {
+                    #[allow(clippy::cast_possible_truncation)]
+                    { *digit = b'0' + (value % 10) as u8; }
+                    value /= 10;
+                }
+                len
+            }
+
+            #[inline]
+            fn to_decimal_array(self) -> (Self::Array, usize) {
+                let mut array = [0; <$SelfT as Log10>::MAX_DECIMAL_LEN];
+                let len = self.write_decimal(&mut array);
+                (array, len)
+            }
+        }
+
+        forward_ref_decimal!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+/// Implements `DecimalFormat` trait for signed integer type
+macro_rules! impl_signed_decimal {
+    ($($SelfT: ty),+) => {$(
+        impl DecimalFormat for $SelfT {
+            type Array = [u8; <$SelfT as Log10>::MAX_DECIMAL_LEN];
+
+            #[inline]
+            fn decimal_len(self) -> usize {
+                usize::from(self < 0) + self.unsigned_abs().decimal_len()
+            }
+
+            #[inline]
+            fn write_decimal(self, buffer: &mut [u8]) -> usize {
+                if self < 0 {
+                    buffer[0] = b'-';
+                    1 + self.unsigned_abs().write_decimal(&mut buffer[1..])
+                } else {
+                    self.unsigned_abs().write_decimal(buffer)
+                }
+            }
+
+            #[inline]
+            fn to_decimal_array(self) -> (Self::Array, usize) {
+                let mut array = [0; <$SelfT as Log10>::MAX_DECIMAL_LEN];
+                let len = self.write_decimal(&mut array);
+                (array, len)
+            }
+        }
+
+        forward_ref_decimal!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_unsigned_decimal! { u8, u16, u32, u64, u128, usize }
+impl_signed_decimal! { i8, i16, i32, i64, i128, isize }
@@ -0,0 +1,227 @@
+// Copyright 2022 Redglyph
+//
+// Access to the decimal digits of integers, from the most significant one
+
+use crate::{pow10, Log10};
+use alloc::boxed::Box;
+use core::iter::FusedIterator;
+
+// =============================================================================================
+
+/// Trait that gives access to the decimal digits of the magnitude of integers, from the most
+/// significant one.
+///
+/// The number of digits is given by [`log10`](Log10::log10), and the digits are extracted with
+/// divisions by the powers of ten. The sign is ignored, and zero has a single digit `0`.
+///
+/// # Examples
+/// ```
+/// use ilog::LeadingDigits;
+///
+/// assert_eq!(31415_u32.leading_digit(), 3);
+/// assert_eq!((-31415_i32).leading_digits(3), 314);
+/// assert_eq!(31415_u64.nth_decimal_digit(1), Some(1));
+/// assert!(31415_u16.decimal_digits().eq([3, 1, 4, 1, 5]));
+/// ```
+pub trait LeadingDigits {
+    /// Unsigned type of the same width, which can hold the magnitude of any value.
+    type Unsigned;
+
+    /// Returns the most significant decimal digit of the magnitude, or 0 for zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LeadingDigits;
+    /// assert_eq!(1999_u32.leading_digit(), 1);
+    /// assert_eq!(i8::MIN.leading_digit(), 1);
+    /// assert_eq!(0_u8.leading_digit(), 0);
+    /// ```
+    fn leading_digit(self) -> u8;
+
+    /// Returns the number made of the `n` most significant decimal digits of the magnitude, or the
+    /// whole magnitude if it has fewer than `n` digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LeadingDigits;
+    /// assert_eq!(123456_u32.leading_digits(2), 12);
+    /// assert_eq!(123456_u32.leading_digits(10), 123456);
+    /// assert_eq!(i8::MIN.leading_digits(3), 128_u8);
+    /// assert_eq!(123456_u32.leading_digits(0), 0);
+    /// ```
+    fn leading_digits(self, n: usize) -> Self::Unsigned;
+
+    /// Returns the decimal digit of the magnitude at `index`, counted from the most significant
+    /// digit at index 0, or `None` if the magnitude has fewer digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LeadingDigits;
+    /// assert_eq!(9876_u16.nth_decimal_digit(0), Some(9));
+    /// assert_eq!((-9876_i16).nth_decimal_digit(3), Some(6));
+    /// assert_eq!(9876_u16.nth_decimal_digit(4), None);
+    /// ```
+    fn nth_decimal_digit(self, index: usize) -> Option<u8>;
+
+    /// Returns an iterator over the decimal digits of the magnitude, from the most significant one,
+    /// which doesn't allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LeadingDigits;
+    /// let digits = u64::MAX.decimal_digits();
+    /// assert_eq!(digits.len(), 20);
+    /// assert!(digits.rev().take(3).eq([5, 1, 6]));
+    /// assert!(0_u8.decimal_digits().eq([0]));
+    /// ```
+    fn decimal_digits(self) -> DecimalDigits;
+}
+
+/// Iterator over the decimal digits of an integer magnitude, from the most significant one.
+///
+/// It's created by [`LeadingDigits::decimal_digits`]. It can also be iterated from the least
+/// significant digit with [`rev`](Iterator::rev).
+#[derive(Clone, Debug)]
+pub struct DecimalDigits {
+    /// Remaining digits
+    value: u128,
+    /// Number of remaining digits
+    len: usize,
+}
+
+impl DecimalDigits {
+    /// Creates an iterator over the `len` digits of `value`.
+    fn new(value: u128, len: usize) -> Self {
+        DecimalDigits { value, len }
+    }
+}
+
+impl Iterator for DecimalDigits {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // the value has `len` remaining digits, so 10^(len - 1) fits
+        let pow = pow10(self.len).unwrap_or(u128::MAX);
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.value / pow) as u8;
+        self.value %= pow;
+        Some(digit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl DoubleEndedIterator for DecimalDigits {
+    #[inline]
+    fn next_back(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.value % 10) as u8;
+        self.value /= 10;
+        Some(digit)
+    }
+}
+
+impl ExactSizeIterator for DecimalDigits {}
+
+impl FusedIterator for DecimalDigits {}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `LeadingDigits` trait to references
+macro_rules! forward_ref_leading_digits {
+    ($UnsignedT: ty; $( $t:ty ),+) => {$(
+        impl LeadingDigits for $t {
+            type Unsigned = $UnsignedT;
+
+            #[inline]
+            fn leading_digit(self) -> u8 {
+                LeadingDigits::leading_digit(*self)
+            }
+            #[inline]
+            fn leading_digits(self, n: usize) -> $UnsignedT {
+                LeadingDigits::leading_digits(*self, n)
+            }
+            #[inline]
+            fn nth_decimal_digit(self, index: usize) -> Option<u8> {
+                LeadingDigits::nth_decimal_digit(*self, index)
+            }
+            #[inline]
+            fn decimal_digits(self) -> DecimalDigits {
+                LeadingDigits::decimal_digits(*self)
+            }
+        }
+    )+}
+}
+
+/// Implements `LeadingDigits` trait for integer type, with the unsigned type of the same width
+macro_rules! impl_leading_digits {
+    ($($SelfT: ty, $UnsignedT: ty);+) => {$(
+        impl LeadingDigits for $SelfT {
+            type Unsigned = $UnsignedT;
+
+            #[inline]
+            fn leading_digit(self) -> u8 {
+                let magnitude = self.abs_diff(0);
+                // 10^log10 <= magnitude, so it fits in the type
+                #[allow(clippy::cast_possible_truncation)]
+                let digit = magnitude.checked_log10().map_or(0, |log| magnitude / pow10(log).unwrap_or(u128::MAX) as $UnsignedT);
+                #[allow(clippy::cast_possible_truncation)]
+                { digit as u8 }
+            }
+
+            #[inline]
+            fn leading_digits(self, n: usize) -> $UnsignedT {
+                let magnitude = self.abs_diff(0);
+                let len = magnitude.checked_log10().map_or(1, |log| log + 1);
+                if n >= len {
+                    magnitude
+                } else if n == 0 {
+                    0
+                } else {
+                    // 10^(len - n) <= magnitude, so it fits in the type
+                    #[allow(clippy::cast_possible_truncation)]
+                    { magnitude / pow10(len - n).unwrap_or(u128::MAX) as $UnsignedT }
+                }
+            }
+
+            #[inline]
+            fn nth_decimal_digit(self, index: usize) -> Option<u8> {
+                let magnitude = self.abs_diff(0);
+                let len = magnitude.checked_log10().map_or(1, |log| log + 1);
+                (index < len).then(|| {
+                    // 10^(len - 1 - index) <= magnitude, so it fits in the type
+                    #[allow(clippy::cast_possible_truncation)]
+                    let digit = magnitude / pow10(len - 1 - index).unwrap_or(u128::MAX) as $UnsignedT % 10;
+                    #[allow(clippy::cast_possible_truncation)]
+                    { digit as u8 }
+                })
+            }
+
+            #[inline]
+            fn decimal_digits(self) -> DecimalDigits {
+                let magnitude = self.abs_diff(0);
+                #[allow(clippy::cast_lossless)]
+                DecimalDigits::new(magnitude as u128, magnitude.checked_log10().map_or(1, |log| log + 1))
+            }
+        }
+
+        forward_ref_leading_digits!($UnsignedT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_leading_digits! {
+    u8, u8; i8, u8; u16, u16; i16, u16; u32, u32; i32, u32;
+    u64, u64; i64, u64; u128, u128; i128, u128; usize, usize; isize, usize
+}
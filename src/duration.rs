@@ -0,0 +1,87 @@
+// Copyright 2022 Redglyph
+//
+// Human-readable durations, with a unit selected from the decimal magnitude
+
+use crate::notation::{round_significand, write_significand, MAX_DIGITS};
+use crate::Rounding;
+use core::fmt;
+use core::time::Duration;
+
+// =============================================================================================
+
+/// Units of the durations, for the powers of 1000 of nanoseconds
+const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+
+/// Maximum length of the output: digits, decimal point and unit
+const MAX_LEN: usize = MAX_DIGITS + 4;
+
+/// Adapter that displays a [`Duration`] in nanoseconds, microseconds, milliseconds or seconds,
+/// with a fixed number of significant digits: `1.23ms`.
+///
+/// The unit is selected from [`log10`](crate::Log10::log10) of the total number of nanoseconds,
+/// and the value is rounded exactly to the number of significant digits, 3 by default. Since the
+/// duration is an integer number of nanoseconds, there are no fractional digits below the
+/// nanosecond. The seconds are the largest unit, so long durations can have more digits in
+/// their integer part, padded with zeros. When the rounding reaches the next power of 1000, the
+/// next unit is used instead. The adapter supports the width, fill and alignment parameters.
+///
+/// # Examples
+/// ```
+/// use core::time::Duration;
+/// use ilog::{HumanDuration, Rounding};
+///
+/// assert_eq!(HumanDuration::new(Duration::from_nanos(1234567)).to_string(), "1.23ms");
+/// assert_eq!(HumanDuration::new(Duration::from_nanos(5)).to_string(), "5ns");
+/// assert_eq!(HumanDuration::new(Duration::from_nanos(1500)).significant_digits(5).to_string(), "1.500µs");
+/// assert_eq!(HumanDuration::new(Duration::from_nanos(999_999)).to_string(), "1.00ms");
+/// assert_eq!(HumanDuration::new(Duration::from_nanos(999_999)).rounding(Rounding::Floor).to_string(), "999µs");
+/// assert_eq!(HumanDuration::new(Duration::from_secs(12345)).to_string(), "12300s");
+/// assert_eq!(format!("{:>8}", HumanDuration::new(Duration::from_millis(250))), "   250ms");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HumanDuration {
+    duration: Duration,
+    digits: usize,
+    rounding: Rounding,
+}
+
+impl HumanDuration {
+    /// Creates an adapter for `duration`, with 3 significant digits rounded half to even.
+    #[must_use]
+    pub const fn new(duration: Duration) -> Self {
+        HumanDuration { duration, digits: 3, rounding: Rounding::HalfEven }
+    }
+
+    /// Sets the number of significant digits.
+    ///
+    /// # Panics
+    /// This function panics if `digits` is zero or greater than 39, the number of digits of `u128::MAX`.
+    #[must_use]
+    pub fn significant_digits(self, digits: usize) -> Self {
+        assert!((1..=MAX_DIGITS).contains(&digits), "the number of significant digits must be in 1..=39");
+        HumanDuration { digits, ..self }
+    }
+
+    /// Sets the rounding mode of the last significant digit.
+    #[must_use]
+    pub fn rounding(self, rounding: Rounding) -> Self {
+        HumanDuration { rounding, ..self }
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (significand, exp) = round_significand(self.duration.as_nanos(), false, self.digits, self.rounding);
+        let unit = (exp / 3).min(UNITS.len() - 1);
+        let int_len = exp - 3 * unit + 1;
+        // no fractional digits below the nanosecond
+        let fraction = self.digits.saturating_sub(int_len).min(3 * unit);
+        let mut buffer = [0; MAX_LEN];
+        let mut pos = write_significand(&mut buffer, 0, significand, int_len + fraction, int_len);
+        let unit = UNITS[unit].as_bytes();
+        buffer[pos..pos + unit.len()].copy_from_slice(unit);
+        pos += unit.len();
+        // the buffer only contains ASCII characters and the UTF-8 micro sign
+        f.pad(core::str::from_utf8(&buffer[..pos]).unwrap_or_default())
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2022 Redglyph
+//
+// Decimal formatting with digit grouping
+
+use crate::{DecimalFormat, Log10};
+use core::fmt;
+
+// =============================================================================================
+
+/// Maximum length of the decimal representation of the integer primitives, for `i128::MIN`
+const MAX_LEN: usize = i128::MAX_DECIMAL_LEN;
+
+/// Sizes of the digit groups, from the right: the first group has `first` digits, and all the
+/// other groups have `rest` digits.
+///
+/// # Examples
+/// ```
+/// use ilog::{Grouped, GroupPattern};
+///
+/// assert_eq!(Grouped::new(1234567).to_string(), "1,234,567");
+/// assert_eq!(Grouped::new(1234567).pattern(GroupPattern::INDIAN).to_string(), "12,34,567");
+/// assert_eq!(Grouped::new(1234567).pattern(GroupPattern::new(4, 4)).to_string(), "123,4567");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupPattern {
+    first: usize,
+    rest: usize,
+}
+
+impl GroupPattern {
+    /// Groups of 3 digits: `1,234,567`.
+    pub const THOUSANDS: Self = Self { first: 3, rest: 3 };
+
+    /// Indian grouping in lakhs and crores, a group of 3 digits then groups of 2 digits: `12,34,567`.
+    pub const INDIAN: Self = Self { first: 3, rest: 2 };
+
+    /// Creates a pattern with a first group of `first` digits, then groups of `rest` digits.
+    ///
+    /// # Panics
+    /// This function panics if `first` or `rest` is zero.
+    #[must_use]
+    pub const fn new(first: usize, rest: usize) -> Self {
+        assert!(first > 0 && rest > 0, "digit groups can't be empty");
+        Self { first, rest }
+    }
+
+    /// Returns the number of separators between the groups of `digits` digits.
+    #[inline]
+    const fn separators(self, digits: usize) -> usize {
+        if digits <= self.first { 0 } else { 1 + (digits - self.first - 1) / self.rest }
+    }
+
+    /// Returns `true` if a separator goes on the left of the digit at `index`, counted from the right.
+    #[inline]
+    const fn is_boundary(self, index: usize) -> bool {
+        index >= self.first && (index - self.first) % self.rest == 0
+    }
+}
+
+/// Adapter that formats an integer in decimal with separators between the groups of digits.
+///
+/// The total length and the positions of the separators are computed from the number of digits,
+/// given by [`log10`](crate::Log10::log10). The adapter implements [`Display`](fmt::Display), which
+/// supports the width, fill and alignment parameters, and can write into a byte buffer with
+/// [`write_to`](Grouped::write_to) without `core::fmt`.
+///
+/// # Examples
+/// ```
+/// use ilog::{Grouped, GroupPattern};
+///
+/// assert_eq!(format!("{}", Grouped::new(-1234567_i32)), "-1,234,567");
+/// assert_eq!(format!("{:>8}", Grouped::new(12345_u16).separator('.')), "  12.345");
+///
+/// let grouped = Grouped::new(1234567_u64).separator(' ').pattern(GroupPattern::INDIAN);
+/// let mut buffer = [0_u8; 16];
+/// let len = grouped.write_to(&mut buffer);
+/// assert_eq!(&buffer[..len], b"12 34 567");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Grouped<T> {
+    value: T,
+    separator: char,
+    pattern: GroupPattern,
+}
+
+impl<T: DecimalFormat + Copy + PartialOrd + Default> Grouped<T> {
+    /// Creates an adapter for `value`, with groups of thousands separated by commas.
+    pub fn new(value: T) -> Self {
+        Grouped { value, separator: ',', pattern: GroupPattern::THOUSANDS }
+    }
+
+    /// Sets the separator between the groups.
+    #[must_use]
+    pub fn separator(self, separator: char) -> Self {
+        Grouped { separator, ..self }
+    }
+
+    /// Sets the sizes of the groups.
+    #[must_use]
+    pub fn pattern(self, pattern: GroupPattern) -> Self {
+        Grouped { pattern, ..self }
+    }
+
+    /// Returns the length in bytes of the formatted integer, including the sign and the separators,
+    /// which are encoded in UTF-8.
+    pub fn output_len(&self) -> usize {
+        let len = self.value.decimal_len();
+        let digits = self.digits(len);
+        len + self.pattern.separators(digits) * self.separator.len_utf8()
+    }
+
+    /// Writes the formatted integer at the beginning of `buffer`, and returns its length in bytes.
+    ///
+    /// # Panics
+    /// This function panics if `buffer` is shorter than [`output_len`](Self::output_len).
+    pub fn write_to(&self, buffer: &mut [u8]) -> usize {
+        let mut decimal = [0; MAX_LEN];
+        let len = self.value.write_decimal(&mut decimal);
+        let sign = len - self.digits(len);
+        let total = self.output_len();
+        let mut separator = [0; 4];
+        let separator = self.separator.encode_utf8(&mut separator).as_bytes();
+        // writes the digits from right to left, with the separators
+        let mut pos = total;
+        for (index, &digit) in decimal[sign..len].iter().rev().enumerate() {
+            if self.pattern.is_boundary(index) {
+                pos -= separator.len();
+                buffer[pos..pos + separator.len()].copy_from_slice(separator);
+            }
+            pos -= 1;
+            buffer[pos] = digit;
+        }
+        buffer[..sign].copy_from_slice(&decimal[..sign]);
+        total
+    }
+
+    /// Returns the number of digits, from the length of the decimal representation
+    #[inline]
+    fn digits(&self, len: usize) -> usize {
+        len - usize::from(self.value < T::default())
+    }
+}
+
+impl<T: DecimalFormat + Copy + PartialOrd + Default> fmt::Display for Grouped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // digits and sign, and separators between the digits (4 bytes max in UTF-8)
+        let mut buffer = [0; MAX_LEN + (MAX_LEN - 1) * 4];
+        let len = self.write_to(&mut buffer);
+        // the buffer only contains ASCII digits, an ASCII sign and UTF-8 separators
+        f.pad(core::str::from_utf8(&buffer[..len]).unwrap_or_default())
+    }
+}
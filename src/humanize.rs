@@ -0,0 +1,188 @@
+// Copyright 2022 Redglyph
+//
+// Humanization of integers with SI and IEC unit prefixes
+
+use crate::{pow10, DecimalFormat, Log10, Log2, Rounding};
+use core::fmt;
+
+// =============================================================================================
+
+/// SI prefixes of the powers of 1000, up to quetta (10^30)
+const SI_PREFIXES: [&str; 11] = ["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+
+/// IEC prefixes of the powers of 1024, up to quebi (2^100)
+const IEC_PREFIXES: [&str; 11] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi"];
+
+/// Index of the largest prefix
+const MAX_INDEX: usize = SI_PREFIXES.len() - 1;
+
+/// Maximum number of fractional digits
+const MAX_FRACTION_DIGITS: usize = 20;
+
+/// Maximum length of the output: sign, 9 integer digits for `u128::MAX`, decimal point,
+/// fractional digits and prefix
+const MAX_LEN: usize = 13 + MAX_FRACTION_DIGITS;
+
+/// Systems of unit prefixes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum System {
+    /// Decimal prefixes, powers of 1000
+    Si,
+    /// Binary prefixes, powers of 1024
+    Iec,
+}
+
+impl System {
+    /// Returns the index of the largest prefix not greater than `magnitude`.
+    #[inline]
+    fn index(self, magnitude: u128) -> usize {
+        let index = match self {
+            System::Si => magnitude.checked_log10().unwrap_or(0) / 3,
+            System::Iec => magnitude.checked_log2().unwrap_or(0) / 10,
+        };
+        index.min(MAX_INDEX)
+    }
+
+    /// Returns the value of the prefix of index `index`.
+    #[inline]
+    fn factor(self, index: usize) -> u128 {
+        match self {
+            // 10^30 fits in a u128
+            System::Si => pow10(3 * index).unwrap_or(u128::MAX),
+            System::Iec => 1 << (10 * index),
+        }
+    }
+
+    /// Returns the symbol of the prefix of index `index`.
+    #[inline]
+    fn prefix(self, index: usize) -> &'static str {
+        match self {
+            System::Si => SI_PREFIXES[index],
+            System::Iec => IEC_PREFIXES[index],
+        }
+    }
+}
+
+/// Adapter that displays an integer with the SI prefix of the largest power of 1000 (`k`, `M`, `G`, ...
+/// up to `Q`), or with the IEC prefix of the largest power of 1024 (`Ki`, `Mi`, `Gi`, ... up to `Qi`),
+/// not greater than its magnitude.
+///
+/// The prefix is selected with [`log10`](Log10::log10) or [`log2`](Log2::log2), and the scaled value
+/// is written with a fixed number of fractional digits, 2 by default, which are computed and rounded
+/// exactly with integer arithmetic. Values below 1000 or 1024 have no prefix and are written without
+/// fractional digits. When the rounding reaches the next power, the next prefix is used instead.
+///
+/// The unit isn't included, so it can be added after the adapter. The adapter supports the width,
+/// fill and alignment parameters.
+///
+/// # Examples
+/// ```
+/// use ilog::{Humanize, Rounding};
+///
+/// assert_eq!(Humanize::si(1234567890_u32).to_string(), "1.23G");
+/// assert_eq!(format!("{}B", Humanize::iec(1536_u16).fraction_digits(1)), "1.5KiB");
+/// assert_eq!(format!("{}B/s", Humanize::si(999)), "999B/s");
+/// assert_eq!(Humanize::si(999_999).fraction_digits(1).to_string(), "1.0M");
+/// assert_eq!(Humanize::si(999_999).fraction_digits(1).rounding(Rounding::Floor).to_string(), "999.9k");
+/// assert_eq!(Humanize::si(-2500_i16).fraction_digits(0).to_string(), "-2k");
+/// assert_eq!(Humanize::iec(u128::MAX).to_string(), "268435456.00Qi");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Humanize<T> {
+    value: T,
+    system: System,
+    fraction_digits: usize,
+    rounding: Rounding,
+}
+
+impl<T> Humanize<T> {
+    /// Creates an adapter for `value` with SI prefixes, 2 fractional digits rounded half to even.
+    pub const fn si(value: T) -> Self {
+        Humanize { value, system: System::Si, fraction_digits: 2, rounding: Rounding::HalfEven }
+    }
+
+    /// Creates an adapter for `value` with IEC prefixes, 2 fractional digits rounded half to even.
+    pub const fn iec(value: T) -> Self {
+        Humanize { value, system: System::Iec, fraction_digits: 2, rounding: Rounding::HalfEven }
+    }
+
+    /// Sets the number of fractional digits of the values with a prefix.
+    ///
+    /// # Panics
+    /// This function panics if `digits` is greater than 20.
+    #[must_use]
+    pub fn fraction_digits(self, digits: usize) -> Self {
+        assert!(digits <= MAX_FRACTION_DIGITS, "the number of fractional digits must be in 0..=20");
+        Humanize { fraction_digits: digits, ..self }
+    }
+
+    /// Sets the rounding mode of the last fractional digit.
+    #[must_use]
+    pub fn rounding(self, rounding: Rounding) -> Self {
+        Humanize { rounding, ..self }
+    }
+}
+
+/// Returns the magnitude divided by the prefix of index `index`, with `digits` fractional digits
+/// in fixed point.
+fn scale(magnitude: u128, negative: bool, system: System, index: usize, digits: usize, rounding: Rounding) -> u128 {
+    let factor = system.factor(index);
+    let mut quotient = magnitude / factor;
+    let mut remainder = magnitude % factor;
+    // long division, where the remainder times 10 doesn't overflow since the factor is at most 2^100
+    for _ in 0..digits {
+        remainder *= 10;
+        quotient = quotient * 10 + remainder / factor;
+        remainder %= factor;
+    }
+    rounding.round(quotient, remainder, factor, negative)
+}
+
+/// Writes the value of sign `negative` and magnitude `magnitude` with the prefix of `system`.
+/// Returns the length.
+fn write_humanized(buffer: &mut [u8; MAX_LEN], negative: bool, magnitude: u128, system: System, digits: usize, rounding: Rounding) -> usize {
+    let mut index = system.index(magnitude);
+    // the values without prefix are exact
+    let digits = if index == 0 { 0 } else { digits };
+    let mut scaled = scale(magnitude, negative, system, index, digits, rounding);
+    // rounding up can reach the next prefix
+    if (1..MAX_INDEX).contains(&index) && scaled / pow10(digits).unwrap_or(1) >= system.factor(1) {
+        index += 1;
+        scaled = scale(magnitude, negative, system, index, digits, rounding);
+    }
+    let mut pos = 0;
+    if negative {
+        buffer[0] = b'-';
+        pos = 1;
+    }
+    let mut decimal = [0; u128::MAX_DECIMAL_DIGITS];
+    let len = scaled.write_decimal(&mut decimal);
+    let int_len = len - digits;
+    buffer[pos..pos + int_len].copy_from_slice(&decimal[..int_len]);
+    pos += int_len;
+    if digits > 0 {
+        buffer[pos] = b'.';
+        buffer[pos + 1..pos + 1 + digits].copy_from_slice(&decimal[int_len..len]);
+        pos += 1 + digits;
+    }
+    let prefix = system.prefix(index).as_bytes();
+    buffer[pos..pos + prefix.len()].copy_from_slice(prefix);
+    pos + prefix.len()
+}
+
+/// Implements `Display` for the humanization adapter of integer type
+macro_rules! impl_humanize {
+    ($($SelfT: ty),+) => {$(
+        impl fmt::Display for Humanize<$SelfT> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut buffer = [0; MAX_LEN];
+                #[allow(unused_comparisons, clippy::cast_lossless)]
+                let len = write_humanized(&mut buffer, self.value < 0, self.value.abs_diff(0) as u128, self.system, self.fraction_digits, self.rounding);
+                // the buffer only contains ASCII characters
+                f.pad(core::str::from_utf8(&buffer[..len]).unwrap_or_default())
+            }
+        }
+    )+}
+}
+
+impl_humanize! { u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize }
@@ -0,0 +1,79 @@
+// Copyright 2022 Redglyph
+//
+// Aggregation of the logarithms and decimal lengths over iterators
+
+use crate::{DecimalFormat, Log2};
+
+// =============================================================================================
+
+/// Extension trait of the iterators, which aggregates the decimal lengths or the logarithms of
+/// their items.
+///
+/// It's automatically implemented for all the iterators. The decimal lengths include the minus
+/// sign of the negative values, and zero has one digit, so the results can be used directly to
+/// align columns or to allocate buffers. The items can be integers, or references to integers
+/// like the items of `slice.iter()`.
+///
+/// # Examples
+/// ```
+/// use ilog::LogIterator;
+///
+/// let column = [12, -1500, 0, 999];
+/// assert_eq!(column.iter().max_decimal_width(), 5);   // "-1500"
+/// assert_eq!(column.iter().total_decimal_len(), 11);  // "12-15000999"
+/// assert_eq!(column.iter().max_log2(), Some(9));      // 999
+/// ```
+pub trait LogIterator: Iterator + Sized {
+    /// Returns the length of the longest decimal representation of the items, including the minus
+    /// sign, or 0 if the iterator is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogIterator;
+    /// assert_eq!([7_u8, 200, 35].into_iter().max_decimal_width(), 3);
+    /// assert_eq!([-7_i8, 0].iter().max_decimal_width(), 2);
+    /// assert_eq!(core::iter::empty::<u32>().max_decimal_width(), 0);
+    /// ```
+    fn max_decimal_width(self) -> usize
+    where
+        Self::Item: DecimalFormat,
+    {
+        self.map(DecimalFormat::decimal_len).max().unwrap_or(0)
+    }
+
+    /// Returns the total length of the decimal representations of the items, including the minus
+    /// signs, which is the exact size of a buffer holding all of them without separators.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogIterator;
+    /// let values = [10_i32, -5, 0];
+    /// let separators = values.len() - 1;
+    /// assert_eq!(values.iter().total_decimal_len() + separators, "10,-5,0".len());
+    /// ```
+    fn total_decimal_len(self) -> usize
+    where
+        Self::Item: DecimalFormat,
+    {
+        self.map(DecimalFormat::decimal_len).sum()
+    }
+
+    /// Returns the largest base 2 logarithm of the positive items, or `None` if there are no
+    /// positive items. The zero and negative items, whose logarithm is undefined, are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogIterator;
+    /// assert_eq!([1_u32, 1024, 5].into_iter().max_log2(), Some(10));
+    /// assert_eq!([-4096_i32, 3].iter().max_log2(), Some(1));
+    /// assert_eq!([0_u8, 0].into_iter().max_log2(), None);
+    /// ```
+    fn max_log2(self) -> Option<usize>
+    where
+        Self::Item: Log2,
+    {
+        self.filter_map(Log2::checked_log2).max()
+    }
+}
+
+impl<I: Iterator> LogIterator for I {}
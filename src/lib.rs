@@ -1,207 +1,544 @@
 // Copyright 2022 Redglyph
 //
 // Base 10 and 2 logarithm functions for integer types and their references:
-// u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize
+// u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, Duration
 
 #![warn(clippy::pedantic)]
 #![allow(clippy::unreadable_literal)]
 #![allow(clippy::cast_sign_loss)]
 #![no_std]
 
+mod compat;
+mod decimal;
+mod digits;
+mod duration;
+mod grouped;
+mod humanize;
+mod iter;
+mod newtype;
+mod notation;
+mod policy;
+mod primitive;
+mod product;
+mod radix;
+mod rescale;
+mod rounding;
+mod roundlog;
+mod shift;
+mod sigfigs;
 mod tests;
+mod valuation;
+
+pub use compat::ILog;
+pub use decimal::DecimalFormat;
+pub use digits::{DecimalDigits, LeadingDigits};
+pub use duration::HumanDuration;
+pub use grouped::{GroupPattern, Grouped};
+pub use humanize::Humanize;
+pub use iter::LogIterator;
+pub use notation::{Engineering, Scientific};
+pub use policy::{Checked, LogPolicy, LogWith, Panic, Saturate, Wrapping, ZeroForNonPositive};
+pub use primitive::LogPrimitive;
+pub use product::LogProduct;
+pub use radix::{Alphabet, RadixFormat};
+pub use rescale::Rescale;
+pub use rounding::Rounding;
+pub use roundlog::RoundLog;
+pub use shift::DecimalShift;
+pub use sigfigs::SignificantFigures;
+pub use valuation::Valuation;
 
 extern crate alloc;
 use alloc::boxed::Box;
+use core::time::Duration;
+
+/// Items used by the exported macros, which are not part of the public API
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+}
 
 // =============================================================================================
 
-/// Trait that provides logarithms for integer types.
+/// Trait that provides the base 2 logarithm.
 ///
-/// The [`log2`](IntLog::log2) and [`log10`](IntLog::log10) methods are optimized for the integer width and are
-/// `[inline]` since the code remains small enough. They typically use constant tables
-/// that are only stored once, even if the methods using them are inlined multiple times.
+/// The [`log2`](Log2::log2) method is optimized for the integer width and is `[inline]`.
 ///
-/// The **checked** versions of the methods, [`checked_log2`](IntLog::checked_log2) and [`checked_log10`](IntLog::checked_log10),
-/// return `None` if the logarithm is undefined for the parameter value, whereas the unchecked
-/// methods mentioned above simply panic or return a wrong value.
-pub trait IntLog {
-    /// Returns the largest integer less than or equal to the base 10 logarithm of the integer.
-    ///
-    /// Logarithms are only defined on positive values, calling `log10` with a null or a negative
-    /// argument may trigger a panic or return a wrong value.
-    /// See [`checked_log10`](Self::checked_log10) for a method that checks its argument first.
+/// The **checked** version of the method, [`checked_log2`](Log2::checked_log2), returns `None` if the
+/// logarithm is undefined for the parameter value, whereas the unchecked method simply panics or
+/// returns a wrong value.
+///
+/// See [`IntLog`] for a trait that combines `Log2` and [`Log10`].
+pub trait Log2 {
+    /// Base 2 logarithm of the largest value of the type.
     ///
     /// # Examples
     /// ```
-    /// # use ilog::IntLog;
-    /// let value: u64 = 100;
-    /// assert_eq!(value.log10(), 2);
-    /// assert_eq!(i32::log10(99), 1);
+    /// # use ilog::Log2;
+    /// assert_eq!(u32::MAX_LOG2, 31);
+    /// assert_eq!(i32::MAX_LOG2, 30);
     /// ```
-    fn log10(self) -> usize;
+    const MAX_LOG2: usize;
 
     /// Returns the largest integer less than or equal to the base 2 logarithm of the integer.
     ///
-    /// Logarithms are only defined on positive values, calling `log10` with a null or a negative
+    /// Logarithms are only defined on positive values, calling `log2` with a null or a negative
     /// argument may trigger a panic or return a wrong value.
     /// See [`checked_log2`](Self::checked_log2) for a method that checks its argument first.
     ///
     /// # Examples
     /// ```
-    /// # use ilog::IntLog;
+    /// # use ilog::Log2;
     /// let value: u64 = 64;
     /// assert_eq!(value.log2(), 6);
     /// assert_eq!(i32::log2(63), 5);
     /// ```
     fn log2(self) -> usize;
 
+    /// Checked base 2 logarithm. Returns the largest integer less than or equal to the base 2
+    /// logarithm of the integer, or `None` if it doesn't exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Log2;
+    /// assert_eq!(64_u32.checked_log2(), Some(6));
+    /// assert_eq!(u64::checked_log2(63), Some(5));
+    /// assert_eq!(0_u32.checked_log2(), None);
+    /// ```
+    fn checked_log2(self) -> Option<usize>;
+}
+
+/// Trait that provides the base 10 logarithm.
+///
+/// The [`log10`](Log10::log10) method is optimized for the integer width and is `[inline]` since
+/// the code remains small enough. It typically uses constant tables that are only stored once, even
+/// if the method using them is inlined multiple times.
+///
+/// The **checked** version of the method, [`checked_log10`](Log10::checked_log10), returns `None` if the
+/// logarithm is undefined for the parameter value, whereas the unchecked method simply panics or
+/// returns a wrong value.
+///
+/// See [`IntLog`] for a trait that combines [`Log2`] and `Log10`.
+pub trait Log10 {
+    /// Base 10 logarithm of the largest value of the type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Log10;
+    /// assert_eq!(u64::MAX_LOG10, 19);
+    /// assert_eq!(i64::MAX_LOG10, 18);
+    /// ```
+    const MAX_LOG10: usize;
+
+    /// Maximum number of decimal digits of a value of the type, without the sign.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Log10;
+    /// assert_eq!(u8::MAX_DECIMAL_DIGITS, 3);  // 255
+    /// assert_eq!(i8::MAX_DECIMAL_DIGITS, 3);  // -128
+    /// ```
+    const MAX_DECIMAL_DIGITS: usize;
+
+    /// Maximum length of the decimal representation of a value of the type, including the
+    /// minus sign for signed types.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Log10;
+    /// assert_eq!(u8::MAX_DECIMAL_LEN, 3);     // 255
+    /// assert_eq!(i8::MAX_DECIMAL_LEN, 4);     // -128
+    ///
+    /// let mut buffer = [0_u8; i64::MAX_DECIMAL_LEN];
+    /// # assert_eq!(buffer.len(), 20);
+    /// ```
+    const MAX_DECIMAL_LEN: usize;
+
+    /// Returns the largest integer less than or equal to the base 10 logarithm of the integer.
+    ///
+    /// Logarithms are only defined on positive values, calling `log10` with a null or a negative
+    /// argument may trigger a panic or return a wrong value.
+    /// See [`checked_log10`](Self::checked_log10) for a method that checks its argument first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Log10;
+    /// let value: u64 = 100;
+    /// assert_eq!(value.log10(), 2);
+    /// assert_eq!(i32::log10(99), 1);
+    /// ```
+    fn log10(self) -> usize;
+
     /// Checked base 10 logarithm. Returns the largest integer less than or equal to the base 10
     /// logarithm of the integer, or `None` if it doesn't exist.
     ///
     /// # Examples
     /// ```
-    /// # use ilog::IntLog;
+    /// # use ilog::Log10;
     /// assert_eq!(100_u32.checked_log10(), Some(2));
     /// assert_eq!(u64::checked_log10(99), Some(1));
     /// assert_eq!(0_u32.checked_log10(), None);
     /// ```
     fn checked_log10(self) -> Option<usize>;
 
-    /// Checked base 10 logarithm. Returns the largest integer less than or equal to the base 10
-    /// logarithm of the integer, or `None` if it doesn't exist.
+    /// Returns an estimate of the base 10 logarithm of the integer, which is either equal to
+    /// [`log10`](Self::log10) or one below it.
+    ///
+    /// The estimate is computed from the position of the most significant bit, without the table
+    /// lookup that corrects it in `log10`. Like `log10`, it's only defined on positive values.
     ///
     /// # Examples
     /// ```
-    /// # use ilog::IntLog;
-    /// assert_eq!(64_u32.checked_log2(), Some(6));
-    /// assert_eq!(u64::checked_log2(63), Some(5));
-    /// assert_eq!(0_u32.checked_log2(), None);
+    /// # use ilog::Log10;
+    /// assert_eq!(1000_u32.log10_estimate(), 2);   // log10 = 3
+    /// assert_eq!(9999_u32.log10_estimate(), 3);   // log10 = 3
     /// ```
-    fn checked_log2(self) -> Option<usize>;
+    fn log10_estimate(self) -> usize;
+
+    /// Returns an upper bound of the base 10 logarithm of the integer, which is either equal to
+    /// [`log10`](Self::log10) or one above it.
+    ///
+    /// This is typically used to size a buffer, which must hold `log10_upper_bound() + 1` decimal
+    /// digits, without the table lookup of `log10`. Like `log10`, it's only defined on positive values.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Log10;
+    /// assert_eq!(999_u32.log10_upper_bound(), 3);   // log10 = 2
+    /// assert_eq!(1000_u32.log10_upper_bound(), 3);  // log10 = 3
+    /// ```
+    fn log10_upper_bound(self) -> usize;
 }
 
+/// Trait that provides logarithms for integer types, in base 2 and 10.
+///
+/// It's automatically implemented for all the types implementing both [`Log2`] and [`Log10`], and
+/// can be used as a bound in generic code that requires both logarithms. To call the methods on
+/// a concrete type, [`Log2`] and [`Log10`] must be in scope.
+///
+/// The [`log2`](Log2::log2) and [`log10`](Log10::log10) methods are optimized for the integer width and are
+/// `[inline]` since the code remains small enough. They typically use constant tables
+/// that are only stored once, even if the methods using them are inlined multiple times.
+///
+/// The **checked** versions of the methods, [`checked_log2`](Log2::checked_log2) and [`checked_log10`](Log10::checked_log10),
+/// return `None` if the logarithm is undefined for the parameter value, whereas the unchecked
+/// methods mentioned above simply panic or return a wrong value.
+///
+/// # Examples
+/// ```
+/// use ilog::IntLog;
+///
+/// fn bits_and_digits(value: impl IntLog + Copy) -> (usize, usize) {
+///     (value.log2() + 1, value.log10() + 1)
+/// }
+///
+/// assert_eq!(bits_and_digits(1000_u32), (10, 4));
+/// assert_eq!(bits_and_digits(&255_u8), (8, 3));
+/// ```
+pub trait IntLog: Log2 + Log10 {}
+
+impl<T: Log2 + Log10> IntLog for T {}
+
 // ---------------------------------------------------------------------------------------------
 
-/// Expands `IntLog` trait to references
+/// Expands `Log2` and `Log10` traits to references
 macro_rules! forward_ref_intlog {
-    ($imp:ident for $( $t:ty ),+) => {$(
-        impl $imp for $t {
+    ($( $t:ty ),+) => {$(
+        impl Log2 for $t {
+            const MAX_LOG2: usize = <<$t as core::ops::Deref>::Target as Log2>::MAX_LOG2;
+
             #[inline]
-            fn log10(self) -> usize {
-                $imp::log10(*self)
+            fn log2(self) -> usize {
+                Log2::log2(*self)
             }
             #[inline]
-            fn log2(self) -> usize {
-                $imp::log2(*self)
+            fn checked_log2(self) -> Option<usize> {
+                Log2::checked_log2(*self)
+            }
+        }
+
+        impl Log10 for $t {
+            const MAX_LOG10: usize = <<$t as core::ops::Deref>::Target as Log10>::MAX_LOG10;
+            const MAX_DECIMAL_DIGITS: usize = <<$t as core::ops::Deref>::Target as Log10>::MAX_DECIMAL_DIGITS;
+            const MAX_DECIMAL_LEN: usize = <<$t as core::ops::Deref>::Target as Log10>::MAX_DECIMAL_LEN;
+
+            #[inline]
+            fn log10(self) -> usize {
+                Log10::log10(*self)
             }
             #[inline]
             fn checked_log10(self) -> Option<usize> {
-                $imp::checked_log10(*self)
+                Log10::checked_log10(*self)
             }
             #[inline]
-            fn checked_log2(self) -> Option<usize> {
-                $imp::checked_log2(*self)
+            fn log10_estimate(self) -> usize {
+                Log10::log10_estimate(*self)
+            }
+            #[inline]
+            fn log10_upper_bound(self) -> usize {
+                Log10::log10_upper_bound(*self)
             }
         }
     )+}
 }
 
-/// Implements `IntLog` trait for unsigned integer type
+/// Returns `10^exp`, or `None` if it doesn't fit in a `u128`. Only meant to be evaluated at compile time.
+const fn pow10_u128(exp: usize) -> Option<u128> {
+    let mut value: u128 = 1;
+    let mut i = 0;
+    while i < exp {
+        value = match value.checked_mul(10) {
+            Some(v) => v,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(value)
+}
+
+/// Checks at compile time that the log10 estimate `(mul * msb_index) >> shr` is either exact or
+/// one below the exact value for all the integers whose MSB index is in `0..=msb`, so that a single
+/// correction by comparison with the next power of ten always gives the exact result.
+const fn is_estimate_exact(msb: usize, mul: usize, shr: usize) -> bool {
+    let mut bit = 0;
+    while bit <= msb {
+        let estimate = (mul * bit) >> shr;
+        let low: u128 = 1 << bit;
+        let high: u128 = low | (low - 1);
+        // the lowest value can't be below the estimate, the highest can't be more than one above
+        if estimate > low.ilog10() as usize || high.ilog10() as usize > estimate + 1 {
+            return false;
+        }
+        bit += 1;
+    }
+    true
+}
+
+/// Implements `Log2` and `Log10` traits for unsigned integer type
 macro_rules! impl_unsigned_log {
-    ($SelfT: ty, $Msb: expr, $ApproxMul: expr, $ApproxShr: expr, $Table: ident) => {
-        impl IntLog for $SelfT {
+    ($SelfT: ty, $Msb: expr, $ApproxMul: expr, $ApproxShr: expr, $Table: ident, $WideT: ty) => {
+        const _: () = assert!(is_estimate_exact($Msb, $ApproxMul, $ApproxShr),
+            concat!("wrong log10 estimate constants for ", stringify!($SelfT)));
+
+        #[cfg(not(feature = "small-tables"))]
+        const _: () = {
+            // the correction reads the table at `estimate + 1`, whose entry must be 10^(estimate + 1) - 1,
+            // or $SelfT::MAX if that power of ten doesn't fit in the type
+            let mut i = 0;
+            while i < $Table.len() {
+                let expected = match pow10_u128(i) {
+                    Some(p) if p - 1 <= <$SelfT>::MAX as u128 => p - 1,
+                    _ => <$SelfT>::MAX as u128,
+                };
+                assert!($Table[i] as u128 == expected, concat!("wrong value in ", stringify!($Table)));
+                i += 1;
+            }
+            assert!((($ApproxMul * $Msb) >> $ApproxShr) + 1 < $Table.len(),
+                concat!(stringify!($Table), " is too short for ", stringify!($SelfT)));
+            assert!(<$SelfT as Log10>::MAX_LOG10 == <$SelfT>::MAX.ilog10() as usize,
+                concat!("MAX_LOG10 doesn't match ", stringify!($Table)));
+        };
+
+        #[cfg(feature = "small-tables")]
+        const _: () = assert!(<$WideT>::BITS == 128 || (($ApproxMul * $Msb) >> $ApproxShr) + 1 < POW10_U64_TABLE.len(),
+            concat!("POW10_U64_TABLE is too short for ", stringify!($SelfT)));
+
+        impl Log2 for $SelfT {
+            const MAX_LOG2: usize = $Msb;
+
+            #[inline]
+            fn log2(self) -> usize {
+                $Msb - self.leading_zeros() as usize
+            }
+
+            #[inline]
+            fn checked_log2(self) -> Option<usize> {
+                if self > 0 { Some(self.log2()) } else { None }
+            }
+        }
+
+        impl Log10 for $SelfT {
+            // index of the last entry 10^k - 1 below the maximum value, whose k is the log10 of the maximum
+            #[cfg(not(feature = "small-tables"))]
+            const MAX_LOG10: usize = {
+                let mut k = $Table.len() - 1;
+                while $Table[k] == <$SelfT>::MAX {
+                    k -= 1;
+                }
+                k
+            };
+            #[cfg(feature = "small-tables")]
+            const MAX_LOG10: usize = <$SelfT>::MAX.ilog10() as usize;
+            const MAX_DECIMAL_DIGITS: usize = Self::MAX_LOG10 + 1;
+            const MAX_DECIMAL_LEN: usize = Self::MAX_DECIMAL_DIGITS;
+
             #[inline]
             fn log10(self) -> usize {
-                let y = ($ApproxMul * ($Msb - self.leading_zeros() as usize)) >> $ApproxShr;
+                let y = self.log10_estimate();
+                #[cfg(not(feature = "small-tables"))]
                 #[allow(clippy::cast_possible_truncation)]
                 // `as $SelfT` below is fine: tables don't contain values > $SelfT::MAX
                 { y + (($Table[y + 1] as $SelfT).wrapping_sub(self) >> $Msb) as usize }
+                #[cfg(feature = "small-tables")]
+                { y + usize::from((self as $WideT).ge_pow10(y + 1)) }
             }
 
             #[inline]
-            fn log2(self) -> usize {
-                $Msb - self.leading_zeros() as usize
+            fn checked_log10(self) -> Option<usize> {
+                if self > 0 { Some(self.log10()) } else { None }
             }
 
             #[inline]
-            fn checked_log10(self) -> Option<usize> {
-                if self > 0 { Some(self.log10()) } else { None }
+            fn log10_estimate(self) -> usize {
+                ($ApproxMul * ($Msb - self.leading_zeros() as usize)) >> $ApproxShr
             }
 
             #[inline]
-            fn checked_log2(self) -> Option<usize> {
-                if self > 0 { Some(self.log2()) } else { None }
+            fn log10_upper_bound(self) -> usize {
+                self.log10_estimate() + 1
             }
         }
 
-        forward_ref_intlog!(IntLog for &$SelfT, &mut $SelfT, Box<$SelfT>);
+        forward_ref_intlog!(&$SelfT, &mut $SelfT, Box<$SelfT>);
     }
 }
 
-/// Implements `IntLog` trait for signed integer type
+/// Implements `Log2` and `Log10` traits for signed integer type
 macro_rules! impl_signed_log {
     ($SelfT: ty, $UnsignedT: ty) => {
-        impl IntLog for $SelfT {
-            #[inline]
-            fn log10(self) -> usize {
-                <$UnsignedT>::log10(self as $UnsignedT)
-            }
+        impl Log2 for $SelfT {
+            const MAX_LOG2: usize = <$UnsignedT>::MAX_LOG2 - 1;
 
             #[inline]
             fn log2(self) -> usize {
                 <$UnsignedT>::log2(self as $UnsignedT)
             }
 
+            #[inline]
+            fn checked_log2(self) -> Option<usize> {
+                if self > 0 { Some(<$UnsignedT>::log2(self as $UnsignedT)) } else { None }
+            }
+        }
+
+        impl Log10 for $SelfT {
+            const MAX_LOG10: usize = <$SelfT>::MAX.ilog10() as usize;
+            const MAX_DECIMAL_DIGITS: usize = <$SelfT>::MIN.unsigned_abs().ilog10() as usize + 1;
+            const MAX_DECIMAL_LEN: usize = Self::MAX_DECIMAL_DIGITS + 1;
+
+            #[inline]
+            fn log10(self) -> usize {
+                <$UnsignedT>::log10(self as $UnsignedT)
+            }
+
             #[inline]
             fn checked_log10(self) -> Option<usize> {
                 if self > 0 { Some(<$UnsignedT>::log10(self as $UnsignedT)) } else { None }
             }
 
             #[inline]
-            fn checked_log2(self) -> Option<usize> {
-                if self > 0 { Some(<$UnsignedT>::log2(self as $UnsignedT)) } else { None }
+            fn log10_estimate(self) -> usize {
+                <$UnsignedT>::log10_estimate(self as $UnsignedT)
+            }
+
+            #[inline]
+            fn log10_upper_bound(self) -> usize {
+                <$UnsignedT>::log10_upper_bound(self as $UnsignedT)
             }
         }
 
-        forward_ref_intlog!(IntLog for &$SelfT, &mut $SelfT, Box<$SelfT>);
+        forward_ref_intlog!(&$SelfT, &mut $SelfT, Box<$SelfT>);
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+// With the `small-tables` feature, all the widths share a single table of powers of ten instead
+// of the `LOG10_*_TABLE` arrays below. Thresholds above 10^19 are computed from two entries.
+
+#[cfg(feature = "small-tables")]
+const POW10_U64_TABLE: [u64; 20] = [1, 10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000,
+    1000000000, 10000000000, 100000000000, 1000000000000, 10000000000000, 100000000000000,
+    1000000000000000, 10000000000000000, 100000000000000000, 1000000000000000000, 10000000000000000000];
+
+#[cfg(feature = "small-tables")]
+const _: () = {
+    let mut i = 0;
+    while i < POW10_U64_TABLE.len() {
+        assert!(matches!(pow10_u128(i), Some(p) if p == POW10_U64_TABLE[i] as u128), "wrong value in POW10_U64_TABLE");
+        i += 1;
+    }
+};
+
+/// Compares a value with a power of ten taken from `POW10_U64_TABLE`
+#[cfg(feature = "small-tables")]
+trait GePow10 {
+    /// Returns `true` if `self >= 10^exp`, or `false` if `10^exp` doesn't fit in the type.
+    fn ge_pow10(self, exp: usize) -> bool;
+}
+
+#[cfg(feature = "small-tables")]
+impl GePow10 for u64 {
+    #[inline]
+    fn ge_pow10(self, exp: usize) -> bool {
+        self >= POW10_U64_TABLE[exp]
+    }
+}
+
+#[cfg(feature = "small-tables")]
+impl GePow10 for u128 {
+    #[inline]
+    fn ge_pow10(self, exp: usize) -> bool {
+        pow10(exp).is_some_and(|p| self >= p)
+    }
+}
+
+/// Returns `10^exp`, or `None` if it doesn't fit in a `u128`. The value is read from the table of
+/// the largest width.
+#[inline]
+fn pow10(exp: usize) -> Option<u128> {
+    #[cfg(not(feature = "small-tables"))]
+    {
+        // the last entry is u128::MAX, since 10^39 doesn't fit
+        (exp < LOG10_U128_TABLE.len() - 1).then(|| LOG10_U128_TABLE[exp] + 1)
+    }
+    #[cfg(feature = "small-tables")]
+    {
+        const HIGH: usize = POW10_U64_TABLE.len() - 1;
+        if exp <= HIGH {
+            Some(u128::from(POW10_U64_TABLE[exp]))
+        } else {
+            POW10_U64_TABLE.get(exp - HIGH).map(|&p| u128::from(p) * u128::from(POW10_U64_TABLE[HIGH]))
+        }
     }
 }
 
 // ---------------------------------------------------------------------------------------------
+#[cfg(not(feature = "small-tables"))]
 const LOG10_U8_TABLE: [u8; 4] = [0, 9, 99, u8::MAX];
 
-impl_unsigned_log! { u8, 7, 19, 6, LOG10_U8_TABLE }
+impl_unsigned_log! { u8, 7, 19, 6, LOG10_U8_TABLE, u64 }
 impl_signed_log! { i8, u8 }
 
+#[cfg(not(feature = "small-tables"))]
 const LOG10_U16_TABLE: [u16; 6] = [0, 9, 99, 999, 9999, u16::MAX];
 
-impl_unsigned_log! { u16, 15, 18, 6, LOG10_U16_TABLE }
+impl_unsigned_log! { u16, 15, 18, 6, LOG10_U16_TABLE, u64 }
 impl_signed_log! { i16, u16 }
-#[cfg(target_pointer_width = "16")]
-impl_unsigned_log! { usize, 15, 18, 6, LOG10_U16_TABLE }
-#[cfg(target_pointer_width = "16")]
-impl_signed_log! { isize, u16 }
 
+#[cfg(not(feature = "small-tables"))]
 const LOG10_U32_TABLE: [u32; 11] = [0, 9, 99, 999, 9999, 99999, 999999, 9999999, 99999999, 999999999, u32::MAX];
 
-impl_unsigned_log! { u32, 31, 19, 6, LOG10_U32_TABLE }
+impl_unsigned_log! { u32, 31, 19, 6, LOG10_U32_TABLE, u64 }
 impl_signed_log! { i32, u32 }
-#[cfg(target_pointer_width = "32")]
-impl_unsigned_log! { usize, 31, 19, 6, LOG10_U32_TABLE }
-#[cfg(target_pointer_width = "32")]
-impl_signed_log! { isize, u32 }
 
+#[cfg(not(feature = "small-tables"))]
 const LOG10_U64_TABLE: [u64; 20] = [0, 9, 99, 999, 9999, 99999, 999999, 9999999, 99999999, 999999999,
     9999999999, 99999999999, 999999999999, 9999999999999, 99999999999999, 999999999999999,
     9999999999999999, 99999999999999999, 999999999999999999, 9999999999999999999];
 
-impl_unsigned_log! { u64, 63, 19, 6, LOG10_U64_TABLE }
+impl_unsigned_log! { u64, 63, 19, 6, LOG10_U64_TABLE, u64 }
 impl_signed_log! { i64, u64 }
-#[cfg(target_pointer_width = "64")]
-impl_unsigned_log! { usize, 63, 19, 6, LOG10_U64_TABLE }
-#[cfg(target_pointer_width = "64")]
-impl_signed_log! { isize, u64 }
 
+#[cfg(not(feature = "small-tables"))]
 const LOG10_U128_TABLE: [u128; 40] = [0, 9, 99, 999, 9999, 99999, 999999, 9999999, 99999999, 999999999,
     9999999999, 99999999999, 999999999999, 9999999999999, 99999999999999, 999999999999999,
     9999999999999999, 99999999999999999, 999999999999999999, 9999999999999999999, 99999999999999999999,
@@ -212,7 +549,281 @@ const LOG10_U128_TABLE: [u128; 40] = [0, 9, 99, 999, 9999, 99999, 999999, 999999
     99999999999999999999999999999999999, 999999999999999999999999999999999999, 9999999999999999999999999999999999999,
     99999999999999999999999999999999999999, u128::MAX];
 
-impl_unsigned_log! { u128, 127, 77, 8, LOG10_U128_TABLE }
+impl_unsigned_log! { u128, 127, 77, 8, LOG10_U128_TABLE, u128 }
 impl_signed_log! { i128, u128 }
 
 // ---------------------------------------------------------------------------------------------
+// Thresholds of the logarithms rounded to the nearest integer. An integer x has a rounded log10
+// of k + 1 if x > 10^k·√10, which is irrational, so the entry k is floor(10^k·√10), or the
+// maximum value if it doesn't fit. Narrower types use the table of u64.
+
+const ROUND_LOG10_U64_TABLE: [u64; 20] = [3, 31, 316, 3162, 31622, 316227, 3162277, 31622776, 316227766, 3162277660,
+    31622776601, 316227766016, 3162277660168, 31622776601683, 316227766016837, 3162277660168379, 31622776601683793, 316227766016837933, 3162277660168379331, u64::MAX];
+
+const ROUND_LOG10_U128_TABLE: [u128; 39] = [3, 31, 316, 3162, 31622, 316227,
+    3162277, 31622776, 316227766, 3162277660, 31622776601, 316227766016,
+    3162277660168, 31622776601683, 316227766016837, 3162277660168379, 31622776601683793, 316227766016837933,
+    3162277660168379331, 31622776601683793319, 316227766016837933199, 3162277660168379331998, 31622776601683793319988, 316227766016837933199889,
+    3162277660168379331998893, 31622776601683793319988935, 316227766016837933199889354, 3162277660168379331998893544, 31622776601683793319988935444, 316227766016837933199889354443,
+    3162277660168379331998893544432, 31622776601683793319988935444327, 316227766016837933199889354443271, 3162277660168379331998893544432718, 31622776601683793319988935444327185, 316227766016837933199889354443271853,
+    3162277660168379331998893544432718533, 31622776601683793319988935444327185337, 316227766016837933199889354443271853371];
+
+const _: () = {
+    let mut i = 0;
+    while i < ROUND_LOG10_U128_TABLE.len() {
+        // floor(10^k·√10) = isqrt(10^(2k + 1)) when it fits, and each entry has one more digit than the previous one
+        if let Some(p) = pow10_u128(2 * i + 1) {
+            assert!(ROUND_LOG10_U128_TABLE[i] == p.isqrt(), "wrong value in ROUND_LOG10_U128_TABLE");
+        } else {
+            assert!(ROUND_LOG10_U128_TABLE[i] / 10 == ROUND_LOG10_U128_TABLE[i - 1], "wrong value in ROUND_LOG10_U128_TABLE");
+        }
+        if i < ROUND_LOG10_U64_TABLE.len() - 1 {
+            assert!(ROUND_LOG10_U64_TABLE[i] as u128 == ROUND_LOG10_U128_TABLE[i], "wrong value in ROUND_LOG10_U64_TABLE");
+        }
+        i += 1;
+    }
+    assert!(ROUND_LOG10_U128_TABLE[ROUND_LOG10_U64_TABLE.len() - 1] > u64::MAX as u128
+        && ROUND_LOG10_U64_TABLE[ROUND_LOG10_U64_TABLE.len() - 1] == u64::MAX, "wrong value in ROUND_LOG10_U64_TABLE");
+};
+
+/// floor(2^63·√2): an integer x with its most significant bit shifted to bit 63 has a rounded log2
+/// one above its log2 if it's greater
+const ROUND_LOG2_U64_THRESHOLD: u64 = 13043817825332782212;
+
+/// floor(2^127·√2), the same threshold for the most significant bit shifted to bit 127
+const ROUND_LOG2_U128_THRESHOLD: u128 = 240615969168004511545033772477625056927;
+
+const _: () = assert!(ROUND_LOG2_U64_THRESHOLD as u128 == (1_u128 << 127).isqrt(), "wrong value of ROUND_LOG2_U64_THRESHOLD");
+const _: () = assert!(ROUND_LOG2_U128_THRESHOLD >> 64 == ROUND_LOG2_U64_THRESHOLD as u128, "wrong value of ROUND_LOG2_U128_THRESHOLD");
+
+// ---------------------------------------------------------------------------------------------
+// Reciprocals of the powers of ten. Since 10^k = 2^k·5^k, the quotient of an integer of `bits`
+// bits by 10^k is the quotient of its `bits - k` upper bits by 5^k, which is computed with a
+// multiplication by `m = ceil(2^s / 5^k)` and a right shift by `s = bits - k + ceil(log2(5^k))`.
+// The product `m·5^k` is between 2^s and 2^s + 2^(s - bits + k), so the quotient is exact for
+// all the numerators (Granlund and Montgomery), and `m` fits in `bits` bits for k >= 1. The entry
+// of k = 0 is (1, 0), which leaves the value unchanged.
+
+/// Returns the multiplier and the shift of the reciprocal of 10^k for integers of `bits` bits.
+/// Only meant to be evaluated at compile time.
+const fn pow10_reciprocal(bits: u32, k: usize) -> (u128, u32) {
+    if k == 0 {
+        return (1, 0);
+    }
+    let mut divisor: u128 = 1;
+    let mut i = 0;
+    while i < k {
+        divisor *= 5;
+        i += 1;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let shift = bits - k as u32 + (128 - (divisor - 1).leading_zeros());
+    // long division of 2^shift by the divisor
+    let mut quotient: u128 = 0;
+    let mut remainder: u128 = 1;
+    let mut i = 0;
+    while i < shift {
+        quotient <<= 1;
+        remainder <<= 1;
+        if remainder >= divisor {
+            remainder -= divisor;
+            quotient |= 1;
+        }
+        i += 1;
+    }
+    let multiplier = if remainder == 0 { quotient } else { quotient + 1 };
+    assert!(multiplier >> (bits - 1) >> 1 == 0, "the reciprocal of a power of ten doesn't fit");
+    (multiplier, shift)
+}
+
+/// Creates the table of the reciprocals of 10^k for k in `0..LEN`, for an unsigned type
+macro_rules! pow10_reciprocal_table {
+    ($UnsignedT: ty, $Len: expr) => {{
+        let mut table: [($UnsignedT, u32); $Len] = [(0, 0); $Len];
+        let mut k = 0;
+        while k < $Len {
+            let (multiplier, shift) = pow10_reciprocal(<$UnsignedT>::BITS, k);
+            // the multiplier fits, which is checked in pow10_reciprocal
+            #[allow(clippy::cast_possible_truncation, clippy::unnecessary_cast)]
+            { table[k] = (multiplier as $UnsignedT, shift); }
+            k += 1;
+        }
+        table
+    }}
+}
+
+// With the `small-tables` feature, the narrower types divide through `u64` and only the tables
+// of `u64` and `u128` are stored.
+#[cfg(not(feature = "small-tables"))]
+const DIV_POW10_U8_TABLE: [(u8, u32); 3] = pow10_reciprocal_table!(u8, 3);
+#[cfg(not(feature = "small-tables"))]
+const DIV_POW10_U16_TABLE: [(u16, u32); 5] = pow10_reciprocal_table!(u16, 5);
+#[cfg(not(feature = "small-tables"))]
+const DIV_POW10_U32_TABLE: [(u32, u32); 10] = pow10_reciprocal_table!(u32, 10);
+const DIV_POW10_U64_TABLE: [(u64, u32); 20] = pow10_reciprocal_table!(u64, 20);
+const DIV_POW10_U128_TABLE: [(u128, u32); 39] = pow10_reciprocal_table!(u128, 39);
+
+/// Returns the 256-bit product of `a` and `b`, as its upper and lower 128 bits
+#[inline]
+const fn mul_wide_u128(a: u128, b: u128) -> (u128, u128) {
+    const LOW: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & LOW);
+    let (b_hi, b_lo) = (b >> 64, b & LOW);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    // the middle sum can't overflow: (2^64 - 1)^2 + 2 * (2^64 - 1) < 2^128
+    let middle = (lo_lo >> 64) + (hi_lo & LOW) + (lo_hi & LOW);
+    (hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64), a.wrapping_mul(b))
+}
+
+/// Returns the upper 128 bits of the 256-bit product of `a` and `b`
+#[inline]
+const fn mul_hi_u128(a: u128, b: u128) -> u128 {
+    mul_wide_u128(a, b).0
+}
+
+// ---------------------------------------------------------------------------------------------
+// `usize` and `isize` use the implementation of the integer types of the same width, selected
+// by `PtrWidth`. The implementations for the other pointer widths remain reachable through
+// `PtrWidth<16>`, `PtrWidth<32>` and `PtrWidth<64>`, so they can be tested on any host.
+
+/// Tag type for a pointer width of `BITS` bits
+pub(crate) struct PtrWidth<const BITS: u32>;
+
+/// Integer types of the same width as `usize` and `isize` for a given pointer width
+pub(crate) trait PtrWidthInt {
+    type Unsigned: IntLog + RoundLog + DecimalShift<Output = Self::Unsigned> + Copy;
+    type Signed: IntLog + Copy;
+
+    /// Converts a `usize` value, which must fit in the pointer width, to `Self::Unsigned`
+    fn unsigned(value: usize) -> Self::Unsigned;
+
+    /// Converts an `isize` value, which must fit in the pointer width, to `Self::Signed`
+    fn signed(value: isize) -> Self::Signed;
+}
+
+/// Implements `PtrWidthInt` for a pointer width
+macro_rules! impl_ptr_width {
+    ($Bits: expr, $UnsignedT: ty, $SignedT: ty) => {
+        impl PtrWidthInt for PtrWidth<$Bits> {
+            type Unsigned = $UnsignedT;
+            type Signed = $SignedT;
+
+            #[inline]
+            #[allow(clippy::cast_possible_truncation)]
+            fn unsigned(value: usize) -> $UnsignedT {
+                value as $UnsignedT
+            }
+
+            #[inline]
+            #[allow(clippy::cast_possible_truncation)]
+            fn signed(value: isize) -> $SignedT {
+                value as $SignedT
+            }
+        }
+    }
+}
+
+impl_ptr_width! { 16, u16, i16 }
+impl_ptr_width! { 32, u32, i32 }
+impl_ptr_width! { 64, u64, i64 }
+
+/// Pointer width of the target
+type TargetPtrWidth = PtrWidth<{ usize::BITS }>;
+
+/// Implements `Log2` and `Log10` traits for `usize` or `isize`, using `TargetPtrWidth`
+macro_rules! impl_size_log {
+    ($SelfT: ty, $Assoc: ident, $Conv: ident) => {
+        impl Log2 for $SelfT {
+            const MAX_LOG2: usize = <<TargetPtrWidth as PtrWidthInt>::$Assoc as Log2>::MAX_LOG2;
+
+            #[inline]
+            fn log2(self) -> usize {
+                TargetPtrWidth::$Conv(self).log2()
+            }
+
+            #[inline]
+            fn checked_log2(self) -> Option<usize> {
+                TargetPtrWidth::$Conv(self).checked_log2()
+            }
+        }
+
+        impl Log10 for $SelfT {
+            const MAX_LOG10: usize = <<TargetPtrWidth as PtrWidthInt>::$Assoc as Log10>::MAX_LOG10;
+            const MAX_DECIMAL_DIGITS: usize = <<TargetPtrWidth as PtrWidthInt>::$Assoc as Log10>::MAX_DECIMAL_DIGITS;
+            const MAX_DECIMAL_LEN: usize = <<TargetPtrWidth as PtrWidthInt>::$Assoc as Log10>::MAX_DECIMAL_LEN;
+
+            #[inline]
+            fn log10(self) -> usize {
+                TargetPtrWidth::$Conv(self).log10()
+            }
+
+            #[inline]
+            fn checked_log10(self) -> Option<usize> {
+                TargetPtrWidth::$Conv(self).checked_log10()
+            }
+
+            #[inline]
+            fn log10_estimate(self) -> usize {
+                TargetPtrWidth::$Conv(self).log10_estimate()
+            }
+
+            #[inline]
+            fn log10_upper_bound(self) -> usize {
+                TargetPtrWidth::$Conv(self).log10_upper_bound()
+            }
+        }
+
+        forward_ref_intlog!(&$SelfT, &mut $SelfT, Box<$SelfT>);
+    }
+}
+
+impl_size_log! { usize, Unsigned, unsigned }
+impl_size_log! { isize, Signed, signed }
+
+// ---------------------------------------------------------------------------------------------
+// Durations, whose logarithms are those of their total number of nanoseconds
+
+impl Log2 for Duration {
+    const MAX_LOG2: usize = Duration::MAX.as_nanos().ilog2() as usize;
+
+    #[inline]
+    fn log2(self) -> usize {
+        self.as_nanos().log2()
+    }
+
+    #[inline]
+    fn checked_log2(self) -> Option<usize> {
+        self.as_nanos().checked_log2()
+    }
+}
+
+impl Log10 for Duration {
+    const MAX_LOG10: usize = Duration::MAX.as_nanos().ilog10() as usize;
+    const MAX_DECIMAL_DIGITS: usize = Self::MAX_LOG10 + 1;
+    const MAX_DECIMAL_LEN: usize = Self::MAX_DECIMAL_DIGITS;
+
+    #[inline]
+    fn log10(self) -> usize {
+        self.as_nanos().log10()
+    }
+
+    #[inline]
+    fn checked_log10(self) -> Option<usize> {
+        self.as_nanos().checked_log10()
+    }
+
+    #[inline]
+    fn log10_estimate(self) -> usize {
+        self.as_nanos().log10_estimate()
+    }
+
+    #[inline]
+    fn log10_upper_bound(self) -> usize {
+        self.as_nanos().log10_upper_bound()
+    }
+}
+
+forward_ref_intlog!(&Duration, &mut Duration, Box<Duration>);
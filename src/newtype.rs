@@ -0,0 +1,83 @@
+// Copyright 2022 Redglyph
+//
+// Macro implementing the logarithm traits for newtypes
+
+// =============================================================================================
+
+/// Implements [`Log2`](crate::Log2), [`Log10`](crate::Log10), and therefore [`IntLog`](crate::IntLog),
+/// and [`LogWith`](crate::LogWith) for single-field tuple structs, by delegating to the inner integer type. The traits are also
+/// implemented for the references and `Box` of the structs, like for the integer primitives.
+///
+/// The inner type must implement `Log2`, `Log10`, `LogWith` and `Copy`. The struct itself doesn't need to
+/// implement `Copy`.
+///
+/// # Examples
+/// ```
+/// use ilog::{impl_intlog_newtype, Log10, Log2};
+///
+/// struct UserId(u64);
+/// struct Port(u16);
+///
+/// impl_intlog_newtype!(UserId(u64), Port(u16));
+///
+/// let id = UserId(12345);
+/// assert_eq!((&id).log10(), 4);
+/// assert_eq!(Port(80).log2(), 6);
+/// assert_eq!(UserId::MAX_DECIMAL_DIGITS, 20);
+/// assert_eq!(Box::new(Port(0)).checked_log10(), None);
+/// ```
+#[macro_export]
+macro_rules! impl_intlog_newtype {
+    ($( $T:ident($Inner:ty) ),+ $(,)?) => {$(
+        $crate::impl_intlog_newtype!(@impl $Inner; $T, &$T, &mut $T, $crate::__private::Box<$T>);
+    )+};
+
+    (@impl $Inner:ty; $( $t:ty ),+) => {$(
+        impl $crate::Log2 for $t {
+            const MAX_LOG2: usize = <$Inner as $crate::Log2>::MAX_LOG2;
+
+            #[inline]
+            fn log2(self) -> usize {
+                <$Inner as $crate::Log2>::log2(self.0)
+            }
+            #[inline]
+            fn checked_log2(self) -> Option<usize> {
+                <$Inner as $crate::Log2>::checked_log2(self.0)
+            }
+        }
+
+        impl $crate::Log10 for $t {
+            const MAX_LOG10: usize = <$Inner as $crate::Log10>::MAX_LOG10;
+            const MAX_DECIMAL_DIGITS: usize = <$Inner as $crate::Log10>::MAX_DECIMAL_DIGITS;
+            const MAX_DECIMAL_LEN: usize = <$Inner as $crate::Log10>::MAX_DECIMAL_LEN;
+
+            #[inline]
+            fn log10(self) -> usize {
+                <$Inner as $crate::Log10>::log10(self.0)
+            }
+            #[inline]
+            fn checked_log10(self) -> Option<usize> {
+                <$Inner as $crate::Log10>::checked_log10(self.0)
+            }
+            #[inline]
+            fn log10_estimate(self) -> usize {
+                <$Inner as $crate::Log10>::log10_estimate(self.0)
+            }
+            #[inline]
+            fn log10_upper_bound(self) -> usize {
+                <$Inner as $crate::Log10>::log10_upper_bound(self.0)
+            }
+        }
+
+        impl $crate::LogWith for $t {
+            #[inline]
+            fn log2_with<P: $crate::LogPolicy>(self) -> P::Output {
+                <$Inner as $crate::LogWith>::log2_with::<P>(self.0)
+            }
+            #[inline]
+            fn log10_with<P: $crate::LogPolicy>(self) -> P::Output {
+                <$Inner as $crate::LogWith>::log10_with::<P>(self.0)
+            }
+        }
+    )+};
+}
@@ -0,0 +1,191 @@
+// Copyright 2022 Redglyph
+//
+// Scientific and engineering notations of integers, with exact rounding
+
+use crate::{pow10, DecimalFormat, Log10, Rounding};
+use core::fmt;
+
+// =============================================================================================
+
+/// Maximum number of significant digits, which is the number of digits of `u128::MAX`
+pub(crate) const MAX_DIGITS: usize = u128::MAX_DECIMAL_DIGITS;
+
+/// Maximum length of a notation: sign, digits, decimal point, `e` and exponent of 2 digits
+const MAX_LEN: usize = MAX_DIGITS + 5;
+
+/// Default number of significant digits
+const DEFAULT_DIGITS: usize = 3;
+
+/// Adapter that displays an integer in scientific notation, with a fixed number of significant
+/// digits: `1.23e9`.
+///
+/// The exponent is given by [`log10`](Log10::log10), and the significand is rounded exactly with
+/// a division by a power of ten, for all the integer types including `u128` and `i128`. The default
+/// is 3 significant digits, rounded to the nearest and ties to even like the `{:e}` format of the
+/// integers in `core`. The adapter supports the width, fill and alignment parameters.
+///
+/// # Examples
+/// ```
+/// use ilog::{Rounding, Scientific};
+///
+/// assert_eq!(Scientific::new(1234567890_u32).to_string(), "1.23e9");
+/// assert_eq!(Scientific::new(-5_i8).to_string(), "-5.00e0");
+/// assert_eq!(Scientific::new(u128::MAX).significant_digits(5).to_string(), "3.4028e38");
+/// assert_eq!(Scientific::new(9999).significant_digits(2).to_string(), "1.0e4");
+/// assert_eq!(Scientific::new(9999).significant_digits(2).rounding(Rounding::Floor).to_string(), "9.9e3");
+/// assert_eq!(format!("{:>8}", Scientific::new(100).significant_digits(1)), "     1e2");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scientific<T> {
+    value: T,
+    digits: usize,
+    rounding: Rounding,
+}
+
+impl<T> Scientific<T> {
+    /// Creates an adapter for `value`, with 3 significant digits rounded half to even.
+    pub const fn new(value: T) -> Self {
+        Scientific { value, digits: DEFAULT_DIGITS, rounding: Rounding::HalfEven }
+    }
+
+    /// Sets the number of significant digits.
+    ///
+    /// # Panics
+    /// This function panics if `digits` is zero or greater than 39, the number of digits of `u128::MAX`.
+    #[must_use]
+    pub fn significant_digits(self, digits: usize) -> Self {
+        assert!((1..=MAX_DIGITS).contains(&digits), "the number of significant digits must be in 1..=39");
+        Scientific { digits, ..self }
+    }
+
+    /// Sets the rounding mode of the significand.
+    #[must_use]
+    pub fn rounding(self, rounding: Rounding) -> Self {
+        Scientific { rounding, ..self }
+    }
+}
+
+/// Adapter that displays an integer in engineering notation, where the exponent is a multiple of 3
+/// and the integer part of the significand has 1 to 3 digits: `12.3e3`.
+///
+/// The significand is rounded exactly to the number of significant digits like [`Scientific`].
+/// When there are fewer significant digits than digits in the integer part, the latter is padded
+/// with zeros.
+///
+/// # Examples
+/// ```
+/// use ilog::{Engineering, Rounding};
+///
+/// assert_eq!(Engineering::new(1234567890_u64).to_string(), "1.23e9");
+/// assert_eq!(Engineering::new(-12345_i16).to_string(), "-12.3e3");
+/// assert_eq!(Engineering::new(123456).significant_digits(4).to_string(), "123.5e3");
+/// assert_eq!(Engineering::new(123456).significant_digits(1).to_string(), "100e3");
+/// assert_eq!(Engineering::new(999999).rounding(Rounding::HalfUp).to_string(), "1.00e6");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Engineering<T> {
+    value: T,
+    digits: usize,
+    rounding: Rounding,
+}
+
+impl<T> Engineering<T> {
+    /// Creates an adapter for `value`, with 3 significant digits rounded half to even.
+    pub const fn new(value: T) -> Self {
+        Engineering { value, digits: DEFAULT_DIGITS, rounding: Rounding::HalfEven }
+    }
+
+    /// Sets the number of significant digits.
+    ///
+    /// # Panics
+    /// This function panics if `digits` is zero or greater than 39, the number of digits of `u128::MAX`.
+    #[must_use]
+    pub fn significant_digits(self, digits: usize) -> Self {
+        assert!((1..=MAX_DIGITS).contains(&digits), "the number of significant digits must be in 1..=39");
+        Engineering { digits, ..self }
+    }
+
+    /// Sets the rounding mode of the significand.
+    #[must_use]
+    pub fn rounding(self, rounding: Rounding) -> Self {
+        Engineering { rounding, ..self }
+    }
+}
+
+/// Rounds `magnitude` to `digits` significant digits, knowing the sign of the value. Returns the
+/// significand, which has at most `digits` digits, and the decimal exponent of its first digit.
+pub(crate) fn round_significand(magnitude: u128, negative: bool, digits: usize, rounding: Rounding) -> (u128, usize) {
+    let mut exp = magnitude.checked_log10().unwrap_or(0);
+    let mut significand = magnitude;
+    if exp >= digits {
+        // 10^(exp + 1 - digits) <= magnitude, so the power exists and the significand isn't zero
+        let divisor = pow10(exp + 1 - digits).unwrap_or(u128::MAX);
+        significand = rounding.div(magnitude, divisor, negative);
+        // rounding up can add a digit, in which case the last one is a zero
+        if significand.log10() == digits {
+            significand /= 10;
+            exp += 1;
+        }
+    }
+    (significand, exp)
+}
+
+/// Writes the first `len` digits of `significand` at `buffer[pos..]`, padded with zeros, with a
+/// decimal point after the first `int_len` digits if there are more. Returns the new position.
+pub(crate) fn write_significand(buffer: &mut [u8], mut pos: usize, significand: u128, len: usize, int_len: usize) -> usize {
+    let mut decimal = [0; MAX_DIGITS];
+    let decimal_len = significand.write_decimal(&mut decimal);
+    let padded = decimal[..decimal_len].iter().chain(core::iter::repeat(&b'0'));
+    for (index, &digit) in padded.take(len).enumerate() {
+        if index == int_len {
+            buffer[pos] = b'.';
+            pos += 1;
+        }
+        buffer[pos] = digit;
+        pos += 1;
+    }
+    pos
+}
+
+/// Writes the notation of the value of sign `negative` and magnitude `magnitude` with `digits`
+/// significant digits, and an exponent which is a multiple of `step`. Returns the length.
+fn write_notation(buffer: &mut [u8; MAX_LEN], negative: bool, magnitude: u128, digits: usize, rounding: Rounding, step: usize) -> usize {
+    let (significand, exp) = round_significand(magnitude, negative, digits, rounding);
+    let int_len = 1 + exp % step;
+    let mut pos = 0;
+    if negative {
+        buffer[0] = b'-';
+        pos = 1;
+    }
+    pos = write_significand(buffer, pos, significand, digits.max(int_len), int_len);
+    buffer[pos] = b'e';
+    pos += 1;
+    pos + (exp - exp % step).write_decimal(&mut buffer[pos..])
+}
+
+/// Implements `Display` for the notation adapters of integer type
+macro_rules! impl_notation {
+    ($($SelfT: ty),+) => {$(
+        impl fmt::Display for Scientific<$SelfT> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut buffer = [0; MAX_LEN];
+                #[allow(unused_comparisons, clippy::cast_lossless)]
+                let len = write_notation(&mut buffer, self.value < 0, self.value.abs_diff(0) as u128, self.digits, self.rounding, 1);
+                // the buffer only contains ASCII characters
+                f.pad(core::str::from_utf8(&buffer[..len]).unwrap_or_default())
+            }
+        }
+
+        impl fmt::Display for Engineering<$SelfT> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut buffer = [0; MAX_LEN];
+                #[allow(unused_comparisons, clippy::cast_lossless)]
+                let len = write_notation(&mut buffer, self.value < 0, self.value.abs_diff(0) as u128, self.digits, self.rounding, 3);
+                // the buffer only contains ASCII characters
+                f.pad(core::str::from_utf8(&buffer[..len]).unwrap_or_default())
+            }
+        }
+    )+}
+}
+
+impl_notation! { u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize }
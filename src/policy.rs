@@ -0,0 +1,209 @@
+// Copyright 2022 Redglyph
+//
+// Logarithms with an explicit policy for the non-positive values
+
+use crate::{Log10, Log2, LogPrimitive};
+use alloc::boxed::Box;
+use core::time::Duration;
+
+// =============================================================================================
+
+/// Behaviour of [`log2_with`](LogWith::log2_with) and [`log10_with`](LogWith::log10_with) for the
+/// values whose logarithm is undefined, i.e. null or negative values.
+///
+/// The crate provides the [`Panic`], [`ZeroForNonPositive`] (or [`Saturate`]), [`Checked`] and
+/// [`Wrapping`] policies.
+pub trait LogPolicy {
+    /// Type of the result.
+    type Output;
+
+    /// Returns the result for a positive value, whose logarithm is `log`.
+    fn positive(log: usize) -> Self::Output;
+
+    /// Returns the result for a null or negative value. `wrapped` is the logarithm of the value
+    /// reinterpreted as an unsigned integer of the same width, or `None` if the value is zero.
+    fn non_positive(wrapped: Option<usize>) -> Self::Output;
+}
+
+/// Policy that panics if the value is null or negative, like the `core` logarithms.
+pub struct Panic;
+
+/// Policy that returns 0 if the value is null or negative.
+pub struct ZeroForNonPositive;
+
+/// Policy that saturates the value to the domain of the logarithm, so the null and negative values
+/// are treated as 1 and give 0. This is the same policy as [`ZeroForNonPositive`].
+pub type Saturate = ZeroForNonPositive;
+
+/// Policy that returns `Some(log)` if the value is positive, or `None` otherwise, like the
+/// **checked** methods.
+pub struct Checked;
+
+/// Policy that reinterprets the negative values as unsigned integers of the same width, like an
+/// `as` cast, and returns the logarithm of `value + 2^BITS`. It returns 0 if the value is null.
+pub struct Wrapping;
+
+impl LogPolicy for Panic {
+    type Output = usize;
+
+    #[inline]
+    fn positive(log: usize) -> usize {
+        log
+    }
+
+    #[inline]
+    fn non_positive(_wrapped: Option<usize>) -> usize {
+        panic!("argument of integer logarithm must be positive")
+    }
+}
+
+impl LogPolicy for ZeroForNonPositive {
+    type Output = usize;
+
+    #[inline]
+    fn positive(log: usize) -> usize {
+        log
+    }
+
+    #[inline]
+    fn non_positive(_wrapped: Option<usize>) -> usize {
+        0
+    }
+}
+
+impl LogPolicy for Checked {
+    type Output = Option<usize>;
+
+    #[inline]
+    fn positive(log: usize) -> Option<usize> {
+        Some(log)
+    }
+
+    #[inline]
+    fn non_positive(_wrapped: Option<usize>) -> Option<usize> {
+        None
+    }
+}
+
+impl LogPolicy for Wrapping {
+    type Output = usize;
+
+    #[inline]
+    fn positive(log: usize) -> usize {
+        log
+    }
+
+    #[inline]
+    fn non_positive(wrapped: Option<usize>) -> usize {
+        wrapped.unwrap_or(0)
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Trait that provides logarithms whose behaviour for null and negative values is selected by a
+/// [`LogPolicy`] type parameter.
+///
+/// # Examples
+/// ```
+/// use ilog::{Checked, LogWith, Panic, Saturate, Wrapping};
+///
+/// assert_eq!(1000_u32.log10_with::<Panic>(), 3);
+/// assert_eq!(0_u32.log10_with::<Saturate>(), 0);
+/// assert_eq!((-8_i32).log2_with::<Checked>(), None);
+/// assert_eq!((-1_i8).log10_with::<Wrapping>(), 2);   // log10(255)
+/// ```
+pub trait LogWith {
+    /// Returns the largest integer less than or equal to the base 2 logarithm of the integer, or
+    /// the result selected by the policy `P` if the integer is null or negative.
+    fn log2_with<P: LogPolicy>(self) -> P::Output;
+
+    /// Returns the largest integer less than or equal to the base 10 logarithm of the integer, or
+    /// the result selected by the policy `P` if the integer is null or negative.
+    fn log10_with<P: LogPolicy>(self) -> P::Output;
+}
+
+/// Expands `LogWith` trait to references
+macro_rules! forward_ref_log_with {
+    ($( $t:ty ),+) => {$(
+        impl LogWith for $t {
+            #[inline]
+            fn log2_with<P: LogPolicy>(self) -> P::Output {
+                LogWith::log2_with::<P>(*self)
+            }
+            #[inline]
+            fn log10_with<P: LogPolicy>(self) -> P::Output {
+                LogWith::log10_with::<P>(*self)
+            }
+        }
+    )+}
+}
+
+/// Implements `LogWith` trait for integer type; `$UnsignedT` is the unsigned type of the same width
+macro_rules! impl_log_with {
+    ($($SelfT: ty, $UnsignedT: ty);+) => {$(
+        impl LogWith for $SelfT {
+            #[inline]
+            fn log2_with<P: LogPolicy>(self) -> P::Output {
+                match Log2::checked_log2(self) {
+                    Some(log) => P::positive(log),
+                    None => P::non_positive(Log2::checked_log2(self as $UnsignedT)),
+                }
+            }
+
+            #[inline]
+            fn log10_with<P: LogPolicy>(self) -> P::Output {
+                match Log10::checked_log10(self) {
+                    Some(log) => P::positive(log),
+                    None => P::non_positive(Log10::checked_log10(self as $UnsignedT)),
+                }
+            }
+        }
+
+        forward_ref_log_with!(&$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_log_with! {
+    u8, u8; i8, u8; u16, u16; i16, u16; u32, u32; i32, u32;
+    u64, u64; i64, u64; u128, u128; i128, u128; usize, usize; isize, usize
+}
+
+/// Durations can't be negative, so only the null duration is non-positive
+impl LogWith for Duration {
+    #[inline]
+    fn log2_with<P: LogPolicy>(self) -> P::Output {
+        match Log2::checked_log2(self) {
+            Some(log) => P::positive(log),
+            None => P::non_positive(None),
+        }
+    }
+
+    #[inline]
+    fn log10_with<P: LogPolicy>(self) -> P::Output {
+        match Log10::checked_log10(self) {
+            Some(log) => P::positive(log),
+            None => P::non_positive(None),
+        }
+    }
+}
+
+forward_ref_log_with!(&Duration, &mut Duration, Box<Duration>);
+
+impl<T: LogPrimitive> LogWith for T {
+    #[inline]
+    fn log2_with<P: LogPolicy>(self) -> P::Output {
+        match Log2::checked_log2(self) {
+            Some(log) => P::positive(log),
+            None => P::non_positive(None),
+        }
+    }
+
+    #[inline]
+    fn log10_with<P: LogPolicy>(self) -> P::Output {
+        match Log10::checked_log10(self) {
+            Some(log) => P::positive(log),
+            None => P::non_positive(None),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2022 Redglyph
+//
+// Logarithms for custom integer types, derived from a few primitive operations
+
+use crate::{is_estimate_exact, pow10, Log10, Log2};
+
+// =============================================================================================
+
+/// Minimal set of operations of an unsigned integer type, from which [`Log2`] and [`Log10`] are
+/// automatically implemented. This is typically used for custom-width integers, like 24- or 48-bit
+/// packed types.
+///
+/// The constants of the base 10 estimate and the powers of ten used to correct it are derived from
+/// [`BITS`](LogPrimitive::BITS), which can't exceed 128. Unlike the integer primitives, the logarithm
+/// traits are not implemented for the references of a `LogPrimitive` type.
+///
+/// # Examples
+/// ```
+/// use ilog::{Log10, Log2, LogPrimitive};
+///
+/// /// 24-bit unsigned integer
+/// #[derive(Clone, Copy, PartialEq, PartialOrd)]
+/// struct U24(u32);
+///
+/// impl LogPrimitive for U24 {
+///     const BITS: u32 = 24;
+///
+///     fn leading_zeros(self) -> u32 {
+///         self.0.leading_zeros() - 8
+///     }
+///
+///     fn from_u128(value: u128) -> Self {
+///         U24(value as u32)
+///     }
+/// }
+///
+/// assert_eq!(U24(999_999).log10(), 5);
+/// assert_eq!(U24(1_000_000).log10(), 6);
+/// assert_eq!(U24(0xff_ffff).log2(), 23);
+/// assert_eq!(U24(0).checked_log2(), None);
+/// assert_eq!(U24::MAX_DECIMAL_DIGITS, 8);
+/// ```
+pub trait LogPrimitive: Copy + PartialOrd {
+    /// Size of the type in bits.
+    const BITS: u32;
+
+    /// Returns the number of leading zeros in the binary representation of `self`, which is
+    /// `BITS` for zero.
+    fn leading_zeros(self) -> u32;
+
+    /// Converts a `u128` value to the type. This is only called with values that fit in `BITS` bits.
+    fn from_u128(value: u128) -> Self;
+}
+
+/// Returns the constants `(mul, shr)` of the estimate `(mul * log2(x)) >> shr` of `log10(x)` for
+/// all the integers whose MSB index is in `0..=msb`, with the smallest `shr`. Only meant to be
+/// evaluated at compile time.
+const fn estimate_constants(msb: usize) -> (usize, usize) {
+    let mut shr = 0;
+    while shr <= 16 {
+        // mul is close to log10(2) * 2^shr
+        let mul = (30103 << shr) / 100000;
+        if is_estimate_exact(msb, mul, shr) {
+            return (mul, shr);
+        }
+        if is_estimate_exact(msb, mul + 1, shr) {
+            return (mul + 1, shr);
+        }
+        shr += 1;
+    }
+    panic!("no log10 estimate constants found");
+}
+
+/// Returns the largest value of a `bits`-bit unsigned integer, as a `u128`
+const fn max_u128(bits: u32) -> u128 {
+    assert!(bits > 0 && bits <= 128, "LogPrimitive::BITS must be in 1..=128");
+    u128::MAX >> (128 - bits)
+}
+
+impl<T: LogPrimitive> Log2 for T {
+    const MAX_LOG2: usize = T::BITS as usize - 1;
+
+    #[inline]
+    fn log2(self) -> usize {
+        (T::BITS - 1 - self.leading_zeros()) as usize
+    }
+
+    #[inline]
+    fn checked_log2(self) -> Option<usize> {
+        if self > T::from_u128(0) { Some(self.log2()) } else { None }
+    }
+}
+
+impl<T: LogPrimitive> Log10 for T {
+    const MAX_LOG10: usize = max_u128(T::BITS).ilog10() as usize;
+    const MAX_DECIMAL_DIGITS: usize = Self::MAX_LOG10 + 1;
+    const MAX_DECIMAL_LEN: usize = Self::MAX_DECIMAL_DIGITS;
+
+    #[inline]
+    fn log10(self) -> usize {
+        let y = self.log10_estimate();
+        match pow10(y + 1) {
+            Some(p) if p <= const { max_u128(T::BITS) } => y + usize::from(self >= T::from_u128(p)),
+            _ => y,
+        }
+    }
+
+    #[inline]
+    fn checked_log10(self) -> Option<usize> {
+        if self > T::from_u128(0) { Some(self.log10()) } else { None }
+    }
+
+    #[inline]
+    fn log10_estimate(self) -> usize {
+        let (mul, shr) = const { estimate_constants(T::BITS as usize - 1) };
+        (mul * self.log2()) >> shr
+    }
+
+    #[inline]
+    fn log10_upper_bound(self) -> usize {
+        self.log10_estimate() + 1
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2022 Redglyph
+//
+// Logarithms of products and powers, which don't overflow
+
+use crate::{mul_wide_u128, pow10, Log10, Log2};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that computes the exact logarithms of products and powers of integers, without
+/// overflow, and predicts whether a product fits in another type.
+///
+/// The products are computed on 256 bits from the magnitudes of the operands, so they never
+/// overflow, and the logarithms are those of the magnitudes of the mathematical results. They're
+/// typically used to select a type wide enough before computing `a * b` or `a.pow(n)`.
+///
+/// # Examples
+/// ```
+/// use ilog::LogProduct;
+///
+/// let (a, b) = (u128::MAX, 1000_u128);
+/// assert_eq!(a.log2_mul(b), Some(137));       // 137 bits + 1
+/// assert_eq!(a.log10_mul(b), Some(41));       // 42 digits
+/// assert_eq!(3_u8.log2_pow(100), Some(158));  // 3^100 < 2^159
+/// assert!(!300_i32.fits_mul::<i16>(200));
+/// assert!(300_i32.fits_mul::<u32>(200));
+/// ```
+pub trait LogProduct: Sized {
+    /// Returns the base 2 logarithm of the magnitude of `self * other`, computed without overflow,
+    /// or `None` if the product is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogProduct;
+    /// assert_eq!(255_u8.log2_mul(255), Some(15));
+    /// assert_eq!(u128::MAX.log2_mul(u128::MAX), Some(255));
+    /// assert_eq!((-4_i8).log2_mul(8), Some(5));
+    /// assert_eq!(0_u32.log2_mul(5), None);
+    /// ```
+    fn log2_mul(self, other: Self) -> Option<usize>;
+
+    /// Returns the base 10 logarithm of the magnitude of `self * other`, computed without overflow,
+    /// or `None` if the product is zero. The product has `log10_mul + 1` decimal digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogProduct;
+    /// assert_eq!(99_u8.log10_mul(99), Some(3));    // 9801
+    /// assert_eq!(100_u8.log10_mul(100), Some(4));  // 10000
+    /// assert_eq!(i128::MIN.log10_mul(i128::MIN), Some(76));
+    /// ```
+    fn log10_mul(self, other: Self) -> Option<usize>;
+
+    /// Returns the base 2 logarithm of the magnitude of `self^n`, computed without overflow, or
+    /// `None` if the power is zero or if its logarithm doesn't fit in a `usize`. Like the `pow`
+    /// method of the integer primitives, `self^0` is 1 for any value.
+    ///
+    /// The logarithm is computed from a lower and an upper bound of the power with 256-bit
+    /// mantissas, so the time doesn't depend on the size of the power. The bounds are exact while
+    /// the power fits in 256 bits. For larger powers, the result is also `None` in the unlikely case
+    /// where the bounds are on both sides of a power of two, which requires the power to be within
+    /// a relative distance of about `n·2^-254` of it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogProduct;
+    /// assert_eq!(10_u32.log2_pow(3), Some(9));          // 1000
+    /// assert_eq!(10_u32.log2_pow(1000), Some(3321));
+    /// assert_eq!((-2_i64).log2_pow(u32::MAX), Some(u32::MAX as usize));
+    /// assert_eq!(0_u8.log2_pow(0), Some(0));
+    /// assert_eq!(0_u8.log2_pow(1), None);
+    /// ```
+    fn log2_pow(self, n: u32) -> Option<usize>;
+
+    /// Returns `true` if `self * other`, computed without overflow, is in the range of the type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::LogProduct;
+    /// assert!(16_u64.fits_mul::<u8>(15));
+    /// assert!(!16_u64.fits_mul::<u8>(16));
+    /// assert!((-16_i64).fits_mul::<i8>(8));
+    /// assert!(!(-16_i64).fits_mul::<u64>(8));
+    /// assert!(u128::MAX.fits_mul::<u128>(1));
+    /// ```
+    fn fits_mul<T: TryFrom<u128> + TryFrom<i128>>(self, other: Self) -> bool;
+}
+
+/// Returns the base 10 logarithm of the product of `a` and `b`, or `None` if it's zero.
+fn log10_mul_u128(a: u128, b: u128) -> Option<usize> {
+    let (log_a, log_b) = (a.checked_log10()?, b.checked_log10()?);
+    // 10^(log_a + log_b) <= a * b < 10^(log_a + log_b + 2)
+    let (pow_hi, pow_lo) = mul_wide_u128(pow10(log_a)?, pow10(log_b)?);
+    let threshold = (pow_hi * 10 + mul_wide_u128(pow_lo, 10).0, pow_lo.wrapping_mul(10));
+    Some(log_a + log_b + usize::from(mul_wide_u128(a, b) >= threshold))
+}
+
+/// Returns the product of two bounds `m·2^e`, whose mantissa `m` has its most significant bit
+/// set. The dropped bits of the mantissa are truncated, or rounded up if `round_up` is true, so
+/// that the result remains a lower or an upper bound.
+fn mul_bounds(a: (u128, i64), b: (u128, i64), round_up: bool) -> (u128, i64) {
+    let (hi, lo) = mul_wide_u128(a.0, b.0);
+    let exp = a.1 + b.1;
+    // the product of the mantissas is in [2^254, 2^256)
+    let (mantissa, dropped, exp) = if hi >> 127 == 1 {
+        (hi, lo != 0, exp + 128)
+    } else {
+        ((hi << 1) | (lo >> 127), lo << 1 != 0, exp + 127)
+    };
+    if round_up && dropped {
+        match mantissa.checked_add(1) {
+            Some(mantissa) => (mantissa, exp),
+            None => (1 << 127, exp + 1),
+        }
+    } else {
+        (mantissa, exp)
+    }
+}
+
+/// Returns the product of two bounds `m·2^e`, whose 256-bit mantissa `m = (high, low)` has its
+/// most significant bit set, like [`mul_bounds`].
+fn mul_bounds_wide(a: ((u128, u128), i64), b: ((u128, u128), i64), round_up: bool) -> ((u128, u128), i64) {
+    let ((a_hi, a_lo), (b_hi, b_lo)) = (a.0, b.0);
+    let (lo_lo, hi_lo, lo_hi, hi_hi) = (mul_wide_u128(a_lo, b_lo), mul_wide_u128(a_hi, b_lo), mul_wide_u128(a_lo, b_hi), mul_wide_u128(a_hi, b_hi));
+    // 512-bit product in four 128-bit words, from the least significant one
+    let (r1, c1) = lo_lo.0.overflowing_add(hi_lo.1);
+    let (r1, c2) = r1.overflowing_add(lo_hi.1);
+    let (r2, c3) = hi_lo.0.overflowing_add(lo_hi.0);
+    let (r2, c4) = r2.overflowing_add(hi_hi.1);
+    let (r2, c5) = r2.overflowing_add(u128::from(c1) + u128::from(c2));
+    // the product is below 2^512, so the most significant word can't overflow
+    let r3 = hi_hi.0 + u128::from(c3) + u128::from(c4) + u128::from(c5);
+    let exp = a.1 + b.1;
+    // the product of the mantissas is in [2^510, 2^512)
+    let (mantissa, dropped, exp) = if r3 >> 127 == 1 {
+        ((r3, r2), r1 != 0 || lo_lo.1 != 0, exp + 256)
+    } else {
+        (((r3 << 1) | (r2 >> 127), (r2 << 1) | (r1 >> 127)), r1 << 1 != 0 || lo_lo.1 != 0, exp + 255)
+    };
+    if round_up && dropped {
+        match mantissa.1.checked_add(1) {
+            Some(low) => ((mantissa.0, low), exp),
+            None => match mantissa.0.checked_add(1) {
+                Some(high) => ((high, 0), exp),
+                None => ((1 << 127, 0), exp + 1),
+            },
+        }
+    } else {
+        (mantissa, exp)
+    }
+}
+
+/// Returns the base 2 logarithm of `odd^n` from its bounds with 256-bit mantissas, which are exact
+/// while the power fits in 256 bits, or `None` if they're on both sides of a power of two. It's
+/// only used when the 128-bit bounds of the power don't have the same logarithm.
+pub(crate) fn log2_pow_wide(odd: u128, n: u32) -> Option<usize> {
+    let base = ((odd << odd.leading_zeros(), 0), -i64::from(odd.leading_zeros()) - 128);
+    let (mut lower, mut upper) = (base, base);
+    for bit in (0..u32::BITS - 1 - n.leading_zeros()).rev() {
+        lower = mul_bounds_wide(lower, lower, false);
+        upper = mul_bounds_wide(upper, upper, true);
+        if n >> bit & 1 == 1 {
+            lower = mul_bounds_wide(lower, base, false);
+            upper = mul_bounds_wide(upper, base, true);
+        }
+    }
+    if lower.1 == upper.1 { usize::try_from(lower.1 + 255).ok() } else { None }
+}
+
+/// Returns the base 2 logarithm of `a^n`, or `None` if it's zero or if the logarithm overflows.
+fn log2_pow_u128(a: u128, n: u32) -> Option<usize> {
+    if n == 0 {
+        return Some(0);
+    }
+    a.checked_log2()?;
+    // a^n = 2^(t·n) · odd^n
+    let zeros = a.trailing_zeros() as usize;
+    let odd = a >> zeros;
+    let shift = zeros.checked_mul(n as usize)?;
+    if odd == 1 {
+        return Some(shift);
+    }
+    // odd^n is bounded by two 128-bit mantissas, exact while the power fits in 128 bits
+    let base = (odd << odd.leading_zeros(), -i64::from(odd.leading_zeros()));
+    let (mut lower, mut upper) = (base, base);
+    for bit in (0..u32::BITS - 1 - n.leading_zeros()).rev() {
+        lower = mul_bounds(lower, lower, false);
+        upper = mul_bounds(upper, upper, true);
+        if n >> bit & 1 == 1 {
+            lower = mul_bounds(lower, base, false);
+            upper = mul_bounds(upper, base, true);
+        }
+    }
+    let log = if lower.1 == upper.1 {
+        usize::try_from(lower.1 + 127).ok()?
+    } else {
+        log2_pow_wide(odd, n)?
+    };
+    shift.checked_add(log)
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `LogProduct` trait to references
+macro_rules! forward_ref_log_product {
+    ($( $t:ty ),+) => {$(
+        impl LogProduct for $t {
+            #[inline]
+            fn log2_mul(self, other: Self) -> Option<usize> {
+                LogProduct::log2_mul(*self, *other)
+            }
+            #[inline]
+            fn log10_mul(self, other: Self) -> Option<usize> {
+                LogProduct::log10_mul(*self, *other)
+            }
+            #[inline]
+            fn log2_pow(self, n: u32) -> Option<usize> {
+                LogProduct::log2_pow(*self, n)
+            }
+            #[inline]
+            fn fits_mul<T: TryFrom<u128> + TryFrom<i128>>(self, other: Self) -> bool {
+                LogProduct::fits_mul::<T>(*self, *other)
+            }
+        }
+    )+}
+}
+
+/// Implements `LogProduct` trait for integer type, from the magnitudes widened to `u128`
+macro_rules! impl_log_product {
+    ($($SelfT: ty),+) => {$(
+        impl LogProduct for $SelfT {
+            #[inline]
+            fn log2_mul(self, other: Self) -> Option<usize> {
+                #[allow(clippy::cast_lossless)]
+                let (hi, lo) = mul_wide_u128(self.abs_diff(0) as u128, other.abs_diff(0) as u128);
+                if hi > 0 { Some(128 + hi.log2()) } else { lo.checked_log2() }
+            }
+
+            #[inline]
+            fn log10_mul(self, other: Self) -> Option<usize> {
+                #[allow(clippy::cast_lossless)]
+                log10_mul_u128(self.abs_diff(0) as u128, other.abs_diff(0) as u128)
+            }
+
+            #[inline]
+            fn log2_pow(self, n: u32) -> Option<usize> {
+                #[allow(clippy::cast_lossless)]
+                log2_pow_u128(self.abs_diff(0) as u128, n)
+            }
+
+            #[inline]
+            fn fits_mul<T: TryFrom<u128> + TryFrom<i128>>(self, other: Self) -> bool {
+                #[allow(clippy::cast_lossless)]
+                let (hi, lo) = mul_wide_u128(self.abs_diff(0) as u128, other.abs_diff(0) as u128);
+                #[allow(unused_comparisons)]
+                let negative = (self < 0) != (other < 0) && lo > 0;
+                if hi > 0 {
+                    false
+                } else if !negative {
+                    <T as TryFrom<u128>>::try_from(lo).is_ok()
+                } else if lo <= 1 << 127 {
+                    // the magnitude 2^127 wraps to i128::MIN, which is restored by the negation
+                    #[allow(clippy::cast_possible_wrap)]
+                    let product = (lo as i128).wrapping_neg();
+                    <T as TryFrom<i128>>::try_from(product).is_ok()
+                } else {
+                    false
+                }
+            }
+        }
+
+        forward_ref_log_product!(&$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_log_product! { u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize }
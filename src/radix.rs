@@ -0,0 +1,257 @@
+// Copyright 2022 Redglyph
+//
+// Formatting of integers in any radix, with built-in or custom alphabets
+
+use crate::{Log10, Log2};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Digits of the radices up to 62, in increasing order of value.
+const DIGITS_62: &[u8; 62] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Alphabet of the digits used by [`RadixFormat::write_radix`]. The radix is the number of digits,
+/// and each digit is a single byte.
+///
+/// # Examples
+/// ```
+/// use ilog::Alphabet;
+///
+/// assert_eq!(Alphabet::HEX_UPPER.radix(), 16);
+/// assert_eq!(Alphabet::base(36), Some(Alphabet::BASE36));
+///
+/// let dna = Alphabet::new(b"ACGT").unwrap();
+/// assert_eq!(dna.radix(), 4);
+/// assert_eq!(Alphabet::new(b"ABA"), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alphabet<'a> {
+    digits: &'a [u8],
+}
+
+impl Alphabet<'static> {
+    /// Binary digits `01`.
+    pub const BINARY: Self = Self { digits: b"01" };
+
+    /// Octal digits `0-7`.
+    pub const OCTAL: Self = Self { digits: b"01234567" };
+
+    /// Decimal digits `0-9`.
+    pub const DECIMAL: Self = Self { digits: b"0123456789" };
+
+    /// Hexadecimal digits `0-9a-f`.
+    pub const HEX_LOWER: Self = Self { digits: b"0123456789abcdef" };
+
+    /// Hexadecimal digits `0-9A-F`.
+    pub const HEX_UPPER: Self = Self { digits: b"0123456789ABCDEF" };
+
+    /// Base 36 digits `0-9a-z`.
+    pub const BASE36: Self = Self { digits: b"0123456789abcdefghijklmnopqrstuvwxyz" };
+
+    /// Base 58 digits of Bitcoin, `1-9A-Za-z` without `0`, `O`, `I` and `l`.
+    pub const BASE58: Self = Self { digits: b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz" };
+
+    /// Base 62 digits `0-9a-zA-Z`.
+    pub const BASE62: Self = Self { digits: DIGITS_62 };
+
+    /// Returns the alphabet of the radix `radix` made of the digits `0-9`, then `a-z` and `A-Z`,
+    /// or `None` if the radix isn't in `2..=62`.
+    #[must_use]
+    pub const fn base(radix: u32) -> Option<Self> {
+        if radix >= 2 && radix <= 62 {
+            Some(Self { digits: DIGITS_62.split_at(radix as usize).0 })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Alphabet<'a> {
+    /// Creates an alphabet from its digits, in increasing order of value. Returns `None` if there
+    /// are fewer than 2 or more than 256 digits, or if a digit is repeated.
+    #[must_use]
+    pub const fn new(digits: &'a [u8]) -> Option<Self> {
+        if digits.len() < 2 || digits.len() > 256 {
+            return None;
+        }
+        let mut i = 0;
+        while i < digits.len() {
+            let mut j = i + 1;
+            while j < digits.len() {
+                if digits[i] == digits[j] {
+                    return None;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        Some(Self { digits })
+    }
+
+    /// Returns the radix, which is the number of digits.
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub const fn radix(&self) -> u32 {
+        // the length is at most 256
+        self.digits.len() as u32
+    }
+
+    /// Returns the digits, in increasing order of value.
+    #[must_use]
+    pub const fn digits(&self) -> &'a [u8] {
+        self.digits
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Trait that writes the representation of integers in any radix into byte buffers.
+///
+/// The exact length of the representation is computed first with a floor logarithm in the radix,
+/// derived from the base 2 or base 10 logarithm, then the digits are written from right to left.
+/// Negative values are preceded by a minus sign.
+///
+/// # Examples
+/// ```
+/// use ilog::{Alphabet, RadixFormat};
+///
+/// let mut buffer = [0_u8; 16];
+/// let len = 255_u8.write_radix(Alphabet::HEX_UPPER, &mut buffer);
+/// assert_eq!(&buffer[..len], b"FF");
+///
+/// let len = (-35_i32).write_radix(Alphabet::BASE36, &mut buffer);
+/// assert_eq!(&buffer[..len], b"-z");
+///
+/// assert_eq!(u64::MAX.radix_len(58), 11);
+/// ```
+pub trait RadixFormat {
+    /// Returns the length of the representation of the integer in radix `radix`, including the minus
+    /// sign for negative values.
+    ///
+    /// # Panics
+    /// This function panics if `radix` is less than 2.
+    fn radix_len(self, radix: u32) -> usize;
+
+    /// Writes the representation of the integer with the digits of `alphabet` at the beginning of
+    /// `buffer`, and returns its length.
+    ///
+    /// # Panics
+    /// This function panics if `buffer` is shorter than the [`radix_len`](Self::radix_len) of the integer.
+    fn write_radix(self, alphabet: Alphabet, buffer: &mut [u8]) -> usize;
+}
+
+/// Checks if a `u32` radix fits in an unsigned integer type
+macro_rules! fits_radix {
+    ($SelfT: ty, $radix: expr) => {{
+        #[allow(clippy::cast_lossless)]
+        { $radix as u128 <= <$SelfT>::MAX as u128 }
+    }}
+}
+
+/// Expands `RadixFormat` trait to references
+macro_rules! forward_ref_radix {
+    ($( $t:ty ),+) => {$(
+        impl RadixFormat for $t {
+            #[inline]
+            fn radix_len(self, radix: u32) -> usize {
+                RadixFormat::radix_len(*self, radix)
+            }
+            #[inline]
+            fn write_radix(self, alphabet: Alphabet, buffer: &mut [u8]) -> usize {
+                RadixFormat::write_radix(*self, alphabet, buffer)
+            }
+        }
+    )+}
+}
+
+/// Implements `RadixFormat` trait for unsigned integer type
+macro_rules! impl_unsigned_radix {
+    ($($SelfT: ty),+) => {$(
+        impl RadixFormat for $SelfT {
+            #[inline]
+            fn radix_len(self, radix: u32) -> usize {
+                assert!(radix >= 2, "radix must be at least 2");
+                if radix.is_power_of_two() {
+                    let bits = radix.trailing_zeros() as usize;
+                    return self.checked_log2().map_or(1, |log| log / bits + 1);
+                }
+                if radix == 10 {
+                    return self.checked_log10().map_or(1, |log| log + 1);
+                }
+                let Some(log2) = self.checked_log2() else { return 1 };
+                if !fits_radix!($SelfT, radix) {
+                    return 1;
+                }
+                #[allow(clippy::cast_possible_truncation)]
+                let radix = radix as $SelfT;
+                // since radix < 2^(log2(radix) + 1), radix^log <= self for this estimate of the floor
+                // logarithm in the radix, which is then corrected by multiplication
+                let mut log = log2 / (radix.log2() + 1);
+                #[allow(clippy::cast_possible_truncation)]
+                let mut power = radix.pow(log as u32);
+                while let Some(next) = power.checked_mul(radix).filter(|&next| next <= self) {
+                    power = next;
+                    log += 1;
+                }
+                log + 1
+            }
+
+            #[inline]
+            fn write_radix(self, alphabet: Alphabet, buffer: &mut [u8]) -> usize {
+                let radix = alphabet.radix();
+                let len = self.radix_len(radix);
+                let digits = alphabet.digits();
+                let mut value = self;
+                if radix.is_power_of_two() {
+                    let bits = radix.trailing_zeros();
+                    #[allow(clippy::cast_possible_truncation)]
+                    let mask = (radix - 1) as $SelfT;
+                    for digit in buffer[..len].iter_mut().rev() {
+                        #[allow(clippy::cast_possible_truncation)]
+                        { *digit = digits[(value & mask) as usize]; }
+                        value = value.checked_shr(bits).unwrap_or(0);
+                    }
+                } else {
+                    // an alphabet has at most 256 digits, so a radix which isn't a power of two fits in a u8
+                    #[allow(clippy::cast_possible_truncation)]
+                    let radix = radix as $SelfT;
+                    for digit in buffer[..len].iter_mut().rev() {
+                        #[allow(clippy::cast_possible_truncation)]
+                        { *digit = digits[(value % radix) as usize]; }
+                        value /= radix;
+                    }
+                }
+                len
+            }
+        }
+
+        forward_ref_radix!(&$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+/// Implements `RadixFormat` trait for signed integer type
+macro_rules! impl_signed_radix {
+    ($($SelfT: ty),+) => {$(
+        impl RadixFormat for $SelfT {
+            #[inline]
+            fn radix_len(self, radix: u32) -> usize {
+                usize::from(self < 0) + self.unsigned_abs().radix_len(radix)
+            }
+
+            #[inline]
+            fn write_radix(self, alphabet: Alphabet, buffer: &mut [u8]) -> usize {
+                if self < 0 {
+                    buffer[0] = b'-';
+                    1 + self.unsigned_abs().write_radix(alphabet, &mut buffer[1..])
+                } else {
+                    self.unsigned_abs().write_radix(alphabet, buffer)
+                }
+            }
+        }
+
+        forward_ref_radix!(&$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_unsigned_radix! { u8, u16, u32, u64, u128, usize }
+impl_signed_radix! { i8, i16, i32, i64, i128, isize }
@@ -0,0 +1,121 @@
+// Copyright 2022 Redglyph
+//
+// Conversion of fixed-point decimals between scales
+
+use crate::{pow10, Log10, Rounding};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that converts fixed-point decimal values between scales, where a value `v` of scale `s`
+/// represents `v / 10^s`.
+///
+/// Increasing the scale multiplies the value by a power of ten, and the overflow is predicted from
+/// [`log10`](Log10::log10) before multiplying: the product has `log10 + k + 1` digits, so it only
+/// needs an exact comparison when that's the number of digits of the type limit. Decreasing the
+/// scale divides the value by a power of ten, with the selected [`Rounding`] mode.
+///
+/// # Examples
+/// ```
+/// use ilog::{Rescale, Rounding};
+///
+/// // 12.34 with 2 decimals is 12.34000000 with 8 decimals
+/// assert_eq!(1234_i128.rescale(2, 8, Rounding::HalfEven), Some(1234000000));
+/// // -12.345 rounded to 2 decimals
+/// assert_eq!((-12345_i64).rescale(3, 2, Rounding::HalfEven), Some(-1234));
+/// assert_eq!((-12345_i64).rescale(3, 2, Rounding::HalfUp), Some(-1235));
+/// // 3.00 with 2 decimals doesn't fit in an i8 with 3 decimals
+/// assert_eq!(300_i16.rescale(2, 3, Rounding::HalfEven), Some(3000));
+/// assert_eq!(30_i8.rescale(1, 3, Rounding::HalfEven), None);
+/// ```
+pub trait Rescale {
+    /// Type of the converted values, which is the integer type, also for its references.
+    type Output;
+
+    /// Converts the value from `from_scale` decimals to `to_scale` decimals, and rounds it with
+    /// `rounding` when the scale is reduced. Returns `None` if the converted value overflows the type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::{Rescale, Rounding};
+    /// assert_eq!(25_u32.rescale(1, 0, Rounding::HalfEven), Some(2));
+    /// assert_eq!(25_u32.rescale(1, 0, Rounding::Ceil), Some(3));
+    /// assert_eq!(i16::MIN.rescale(0, 1, Rounding::HalfEven), None);
+    /// assert_eq!(u64::MAX.rescale(0, 100, Rounding::HalfEven), None);
+    /// assert_eq!(u64::MAX.rescale(100, 0, Rounding::Ceil), Some(1));
+    /// ```
+    fn rescale(self, from_scale: usize, to_scale: usize, rounding: Rounding) -> Option<Self::Output>;
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `Rescale` trait to references
+macro_rules! forward_ref_rescale {
+    ($SelfT: ty; $( $t:ty ),+) => {$(
+        impl Rescale for $t {
+            type Output = $SelfT;
+
+            #[inline]
+            fn rescale(self, from_scale: usize, to_scale: usize, rounding: Rounding) -> Option<$SelfT> {
+                Rescale::rescale(*self, from_scale, to_scale, rounding)
+            }
+        }
+    )+}
+}
+
+/// Implements `Rescale` trait for integer type, with the unsigned type of the same width
+macro_rules! impl_rescale {
+    ($($SelfT: ty, $UnsignedT: ty);+) => {$(
+        impl Rescale for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn rescale(self, from_scale: usize, to_scale: usize, rounding: Rounding) -> Option<$SelfT> {
+                #[allow(unused_comparisons)]
+                let negative = self < 0;
+                let magnitude = self.abs_diff(0);
+                let rescaled: $UnsignedT = if to_scale >= from_scale {
+                    let k = to_scale - from_scale;
+                    let Some(log) = magnitude.checked_log10() else { return Some(0) };
+                    let limit = if negative { <$SelfT>::MIN.abs_diff(0) } else { <$SelfT>::MAX.abs_diff(0) };
+                    // the product has log + k + 1 digits, and 10^k fits when log + k <= limit.log10()
+                    match (log + k).cmp(&limit.log10()) {
+                        core::cmp::Ordering::Less => {
+                            #[allow(clippy::cast_possible_truncation)]
+                            { magnitude * pow10(k)? as $UnsignedT }
+                        }
+                        core::cmp::Ordering::Equal => {
+                            #[allow(clippy::cast_possible_truncation)]
+                            let pow = pow10(k)? as $UnsignedT;
+                            if magnitude > limit / pow { return None }
+                            magnitude * pow
+                        }
+                        core::cmp::Ordering::Greater => return None,
+                    }
+                } else {
+                    #[allow(clippy::cast_lossless)]
+                    let magnitude = magnitude as u128;
+                    let quotient = match pow10(from_scale - to_scale) {
+                        Some(pow) => rounding.div(magnitude, pow, negative),
+                        // 10^k > 2 * magnitude: any non-zero magnitude is rounded like a tiny fraction
+                        None => rounding.div(u128::from(magnitude > 0), u128::MAX, negative),
+                    };
+                    // the magnitude doesn't increase with a divisor of at least 10
+                    #[allow(clippy::cast_possible_truncation)]
+                    { quotient as $UnsignedT }
+                };
+                // the magnitude is in range, so the conversion only wraps for the minimum value
+                #[allow(clippy::cast_possible_wrap)]
+                let rescaled = rescaled as $SelfT;
+                Some(if negative { rescaled.wrapping_neg() } else { rescaled })
+            }
+        }
+
+        forward_ref_rescale!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_rescale! {
+    u8, u8; i8, u8; u16, u16; i16, u16; u32, u32; i32, u32;
+    u64, u64; i64, u64; u128, u128; i128, u128; usize, usize; isize, usize
+}
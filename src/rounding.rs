@@ -0,0 +1,65 @@
+// Copyright 2022 Redglyph
+//
+// Rounding modes of the integer divisions by powers of ten
+
+// =============================================================================================
+
+/// Rounding mode used when digits are dropped.
+///
+/// The directions `Floor` and `Ceil` refer to the signed value, while `TowardZero`, `AwayFromZero`
+/// and `HalfUp` refer to its magnitude.
+///
+/// # Examples
+/// ```
+/// use ilog::{Rounding, Scientific};
+///
+/// assert_eq!(Scientific::new(-1250).significant_digits(2).to_string(), "-1.2e3");
+/// assert_eq!(Scientific::new(-1250).significant_digits(2).rounding(Rounding::HalfUp).to_string(), "-1.3e3");
+/// assert_eq!(Scientific::new(-1250).significant_digits(2).rounding(Rounding::Floor).to_string(), "-1.3e3");
+/// assert_eq!(Scientific::new(-1250).significant_digits(2).rounding(Rounding::Ceil).to_string(), "-1.2e3");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Rounds to the nearest value, and the ties to the even value (banker's rounding), like
+    /// the `core` formatting of integers in exponential notation.
+    #[default]
+    HalfEven,
+    /// Rounds to the nearest value, and the ties away from zero.
+    HalfUp,
+    /// Rounds toward negative infinity.
+    Floor,
+    /// Rounds toward positive infinity.
+    Ceil,
+    /// Rounds toward zero (truncation).
+    TowardZero,
+    /// Rounds away from zero.
+    AwayFromZero,
+}
+
+impl Rounding {
+    /// Divides `magnitude` by `divisor` and rounds the quotient, knowing the sign of the value.
+    /// Returns the magnitude of the rounded quotient.
+    pub(crate) const fn div(self, magnitude: u128, divisor: u128, negative: bool) -> u128 {
+        self.round(magnitude / divisor, magnitude % divisor, divisor, negative)
+    }
+
+    /// Rounds the truncated `quotient` of a division by `divisor` which left `remainder`, knowing
+    /// the sign of the value. Returns the magnitude of the rounded quotient.
+    pub(crate) const fn round(self, quotient: u128, remainder: u128, divisor: u128, negative: bool) -> u128 {
+        if remainder == 0 {
+            return quotient;
+        }
+        // compares the remainder to the half of the divisor without overflow
+        let other = divisor - remainder;
+        let up = match self {
+            Rounding::HalfEven => remainder > other || (remainder == other && quotient % 2 == 1),
+            Rounding::HalfUp => remainder >= other,
+            Rounding::Floor => negative,
+            Rounding::Ceil => !negative,
+            Rounding::TowardZero => false,
+            Rounding::AwayFromZero => true,
+        };
+        // the quotient can't be u128::MAX when the remainder isn't zero
+        if up { quotient + 1 } else { quotient }
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2022 Redglyph
+//
+// Logarithms rounded to the nearest integer
+
+use crate::{pow10, Log10, Log2, PtrWidthInt, TargetPtrWidth, ROUND_LOG10_U128_TABLE, ROUND_LOG10_U64_TABLE, ROUND_LOG2_U128_THRESHOLD, ROUND_LOG2_U64_THRESHOLD};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that provides the base 2 and base 10 logarithms rounded to the nearest integer, which
+/// are the positions of the values on a logarithmic scale.
+///
+/// The logarithm is rounded up when the value is greater than the geometric mean of the two
+/// surrounding powers, `2^k·√2` or `10^k·√10`. Those thresholds are irrational, so there are no
+/// ties, and their integer parts are precomputed in tables: the methods only compare the value
+/// with one entry after the floor logarithm, without floating-point arithmetic.
+///
+/// Like the floor logarithms, they're only defined on positive values.
+///
+/// # Examples
+/// ```
+/// use ilog::RoundLog;
+///
+/// assert_eq!(316_u32.round_log10(), 2);   // 316 < 100·√10 = 316.2...
+/// assert_eq!(317_u32.round_log10(), 3);
+/// assert_eq!(181_u8.round_log2(), 7);     // 181 < 128·√2 = 181.01...
+/// assert_eq!(182_u8.round_log2(), 8);
+/// assert_eq!(4000_i32.nearest_power_of_ten(), Some(10000));
+/// ```
+pub trait RoundLog {
+    /// Type of the powers of ten, which is the integer type, also for its references.
+    type Output;
+
+    /// Returns the base 2 logarithm of the integer rounded to the nearest integer.
+    ///
+    /// Logarithms are only defined on positive values, calling `round_log2` with a null or a negative
+    /// argument may trigger a panic or return a wrong value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::RoundLog;
+    /// assert_eq!(5_u16.round_log2(), 2);
+    /// assert_eq!(6_u16.round_log2(), 3);
+    /// assert_eq!(u64::MAX.round_log2(), 64);
+    /// ```
+    fn round_log2(self) -> usize;
+
+    /// Checked base 2 logarithm rounded to the nearest integer, or `None` if the logarithm doesn't exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::RoundLog;
+    /// assert_eq!(6_i8.checked_round_log2(), Some(3));
+    /// assert_eq!((-6_i8).checked_round_log2(), None);
+    /// ```
+    fn checked_round_log2(self) -> Option<usize>;
+
+    /// Returns the base 10 logarithm of the integer rounded to the nearest integer.
+    ///
+    /// Logarithms are only defined on positive values, calling `round_log10` with a null or a negative
+    /// argument may trigger a panic or return a wrong value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::RoundLog;
+    /// assert_eq!(3_u8.round_log10(), 0);
+    /// assert_eq!(4_u8.round_log10(), 1);
+    /// assert_eq!(u128::MAX.round_log10(), 39);
+    /// ```
+    fn round_log10(self) -> usize;
+
+    /// Checked base 10 logarithm rounded to the nearest integer, or `None` if the logarithm doesn't exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::RoundLog;
+    /// assert_eq!(4_u32.checked_round_log10(), Some(1));
+    /// assert_eq!(0_u32.checked_round_log10(), None);
+    /// ```
+    fn checked_round_log10(self) -> Option<usize>;
+
+    /// Returns the power of ten which is the nearest to the integer on a logarithmic scale,
+    /// `10^round_log10`, or `None` if the integer isn't positive or if the power doesn't fit in
+    /// the type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::RoundLog;
+    /// assert_eq!(316_u16.nearest_power_of_ten(), Some(100));
+    /// assert_eq!(317_u16.nearest_power_of_ten(), Some(1000));
+    /// assert_eq!(30000_i16.nearest_power_of_ten(), Some(10000));
+    /// assert_eq!(i16::MAX.nearest_power_of_ten(), None);   // 100000
+    /// assert_eq!(u128::MAX.nearest_power_of_ten(), None);
+    /// ```
+    fn nearest_power_of_ten(self) -> Option<Self::Output>;
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `RoundLog` trait to references
+macro_rules! forward_ref_round_log {
+    ($SelfT: ty; $( $t:ty ),+) => {$(
+        impl RoundLog for $t {
+            type Output = $SelfT;
+
+            #[inline]
+            fn round_log2(self) -> usize {
+                RoundLog::round_log2(*self)
+            }
+            #[inline]
+            fn checked_round_log2(self) -> Option<usize> {
+                RoundLog::checked_round_log2(*self)
+            }
+            #[inline]
+            fn round_log10(self) -> usize {
+                RoundLog::round_log10(*self)
+            }
+            #[inline]
+            fn checked_round_log10(self) -> Option<usize> {
+                RoundLog::checked_round_log10(*self)
+            }
+            #[inline]
+            fn nearest_power_of_ten(self) -> Option<$SelfT> {
+                RoundLog::nearest_power_of_ten(*self)
+            }
+        }
+    )+}
+}
+
+/// Implements `RoundLog` trait for unsigned integer type, which is compared in the wider type
+/// of the tables
+macro_rules! impl_unsigned_round_log {
+    ($($SelfT: ty, $WideT: ty, $Table: ident, $Threshold: ident);+) => {$(
+        impl RoundLog for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn round_log2(self) -> usize {
+                let log = self.log2();
+                #[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+                let normalized = (self as $WideT) << (<$WideT>::BITS as usize - 1 - log);
+                log + usize::from(normalized > $Threshold)
+            }
+
+            #[inline]
+            fn checked_round_log2(self) -> Option<usize> {
+                if self > 0 { Some(self.round_log2()) } else { None }
+            }
+
+            #[inline]
+            fn round_log10(self) -> usize {
+                let log = self.log10();
+                #[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+                { log + usize::from(self as $WideT > $Table[log]) }
+            }
+
+            #[inline]
+            fn checked_round_log10(self) -> Option<usize> {
+                if self > 0 { Some(self.round_log10()) } else { None }
+            }
+
+            #[inline]
+            fn nearest_power_of_ten(self) -> Option<$SelfT> {
+                pow10(self.checked_round_log10()?).and_then(|p| <$SelfT>::try_from(p).ok())
+            }
+        }
+
+        forward_ref_round_log!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+/// Implements `RoundLog` trait for signed integer type
+macro_rules! impl_signed_round_log {
+    ($($SelfT: ty, $UnsignedT: ty);+) => {$(
+        impl RoundLog for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn round_log2(self) -> usize {
+                <$UnsignedT>::round_log2(self as $UnsignedT)
+            }
+
+            #[inline]
+            fn checked_round_log2(self) -> Option<usize> {
+                if self > 0 { Some(self.round_log2()) } else { None }
+            }
+
+            #[inline]
+            fn round_log10(self) -> usize {
+                <$UnsignedT>::round_log10(self as $UnsignedT)
+            }
+
+            #[inline]
+            fn checked_round_log10(self) -> Option<usize> {
+                if self > 0 { Some(self.round_log10()) } else { None }
+            }
+
+            #[inline]
+            fn nearest_power_of_ten(self) -> Option<$SelfT> {
+                pow10(self.checked_round_log10()?).and_then(|p| <$SelfT>::try_from(p).ok())
+            }
+        }
+
+        forward_ref_round_log!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_unsigned_round_log! {
+    u8, u64, ROUND_LOG10_U64_TABLE, ROUND_LOG2_U64_THRESHOLD;
+    u16, u64, ROUND_LOG10_U64_TABLE, ROUND_LOG2_U64_THRESHOLD;
+    u32, u64, ROUND_LOG10_U64_TABLE, ROUND_LOG2_U64_THRESHOLD;
+    u64, u64, ROUND_LOG10_U64_TABLE, ROUND_LOG2_U64_THRESHOLD;
+    u128, u128, ROUND_LOG10_U128_TABLE, ROUND_LOG2_U128_THRESHOLD
+}
+
+/// `usize` uses the implementation of the unsigned type of the same width, selected by `TargetPtrWidth`
+impl RoundLog for usize {
+    type Output = usize;
+
+    #[inline]
+    fn round_log2(self) -> usize {
+        TargetPtrWidth::unsigned(self).round_log2()
+    }
+
+    #[inline]
+    fn checked_round_log2(self) -> Option<usize> {
+        TargetPtrWidth::unsigned(self).checked_round_log2()
+    }
+
+    #[inline]
+    fn round_log10(self) -> usize {
+        TargetPtrWidth::unsigned(self).round_log10()
+    }
+
+    #[inline]
+    fn checked_round_log10(self) -> Option<usize> {
+        TargetPtrWidth::unsigned(self).checked_round_log10()
+    }
+
+    #[inline]
+    fn nearest_power_of_ten(self) -> Option<usize> {
+        pow10(self.checked_round_log10()?).and_then(|p| usize::try_from(p).ok())
+    }
+}
+
+forward_ref_round_log!(usize; &usize, &mut usize, Box<usize>);
+
+impl_signed_round_log! { i8, u8; i16, u16; i32, u32; i64, u64; i128, u128; isize, usize }
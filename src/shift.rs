@@ -0,0 +1,283 @@
+// Copyright 2022 Redglyph
+//
+// Multiplications and divisions by powers of ten
+
+use crate::{mul_hi_u128, pow10, PtrWidthInt, TargetPtrWidth, DIV_POW10_U128_TABLE, DIV_POW10_U64_TABLE};
+#[cfg(not(feature = "small-tables"))]
+use crate::{DIV_POW10_U16_TABLE, DIV_POW10_U32_TABLE, DIV_POW10_U8_TABLE};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that shifts integers by decimal digits, which multiplies or divides them by `10^k`.
+///
+/// The divisions don't use the hardware division: the quotient is computed with a multiplication
+/// by a precomputed reciprocal of `10^k` and a right shift, which is exact for all the values of
+/// the type. The reciprocals are indexed by `k`, like the powers of ten used by [`log10`](crate::Log10::log10).
+///
+/// Like the `/` and `%` operators, the quotients are rounded toward zero and the remainders have
+/// the sign of the value.
+///
+/// # Examples
+/// ```
+/// use ilog::DecimalShift;
+///
+/// assert_eq!(123456_u32.div_pow10(3), 123);
+/// assert_eq!((-123456_i64).divrem_pow10(2), (-1234, -56));
+/// assert_eq!(25_u8.checked_mul_pow10(1), Some(250));
+/// assert_eq!(26_u8.checked_mul_pow10(1), None);
+/// ```
+pub trait DecimalShift {
+    /// Type of the shifted values, which is the integer type, also for its references.
+    type Output;
+
+    /// Returns the value divided by `10^k`, rounded toward zero. The result is zero when `10^k`
+    /// exceeds the range of the type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::DecimalShift;
+    /// assert_eq!(u64::MAX.div_pow10(19), 1);
+    /// assert_eq!(u64::MAX.div_pow10(20), 0);
+    /// assert_eq!((-1999_i16).div_pow10(3), -1);
+    /// assert_eq!(7_u8.div_pow10(0), 7);
+    /// ```
+    #[must_use]
+    fn div_pow10(self, k: usize) -> Self::Output;
+
+    /// Returns the quotient and the remainder of the value divided by `10^k`, which are equal to
+    /// `self / 10^k` and `self % 10^k`. When `10^k` exceeds the range of the type, the quotient
+    /// is zero and the remainder is the value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::DecimalShift;
+    /// assert_eq!(98765_u32.divrem_pow10(3), (98, 765));
+    /// assert_eq!(i8::MIN.divrem_pow10(1), (-12, -8));
+    /// assert_eq!(i8::MIN.divrem_pow10(5), (0, i8::MIN));
+    /// ```
+    fn divrem_pow10(self, k: usize) -> (Self::Output, Self::Output);
+
+    /// Returns the value multiplied by `10^k`, or `None` if the product overflows the type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::DecimalShift;
+    /// assert_eq!(12_i16.checked_mul_pow10(3), Some(12000));
+    /// assert_eq!((-3_i16).checked_mul_pow10(4), Some(-30000));
+    /// assert_eq!(4_i16.checked_mul_pow10(4), None);
+    /// assert_eq!(0_u64.checked_mul_pow10(100), Some(0));
+    /// ```
+    fn checked_mul_pow10(self, k: usize) -> Option<Self::Output>;
+}
+
+/// Returns `10^k` in the type, or `None` if it doesn't fit.
+#[inline]
+fn pow10_in<T: TryFrom<u128>>(k: usize) -> Option<T> {
+    pow10(k).and_then(|pow| T::try_from(pow).ok())
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `DecimalShift` trait to references
+macro_rules! forward_ref_shift {
+    ($SelfT: ty; $( $t:ty ),+) => {$(
+        impl DecimalShift for $t {
+            type Output = $SelfT;
+
+            #[inline]
+            fn div_pow10(self, k: usize) -> $SelfT {
+                DecimalShift::div_pow10(*self, k)
+            }
+            #[inline]
+            fn divrem_pow10(self, k: usize) -> ($SelfT, $SelfT) {
+                DecimalShift::divrem_pow10(*self, k)
+            }
+            #[inline]
+            fn checked_mul_pow10(self, k: usize) -> Option<$SelfT> {
+                DecimalShift::checked_mul_pow10(*self, k)
+            }
+        }
+    )+}
+}
+
+/// Implements `DecimalShift` trait for unsigned integer type narrower than 128 bits, which
+/// multiplies by the reciprocals in the type of double width
+macro_rules! impl_unsigned_shift {
+    ($($SelfT: ty, $WideT: ty, $Table: ident);+) => {$(
+        impl DecimalShift for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn div_pow10(self, k: usize) -> $SelfT {
+                match $Table.get(k) {
+                    Some(&(multiplier, shift)) => {
+                        #[allow(clippy::cast_lossless)]
+                        let product = ((self >> k) as $WideT) * multiplier as $WideT;
+                        // the quotient isn't greater than the value
+                        #[allow(clippy::cast_possible_truncation)]
+                        { (product >> shift) as $SelfT }
+                    }
+                    None => 0,
+                }
+            }
+
+            #[inline]
+            fn divrem_pow10(self, k: usize) -> ($SelfT, $SelfT) {
+                match pow10_in::<$SelfT>(k) {
+                    Some(pow) => {
+                        let quotient = self.div_pow10(k);
+                        (quotient, self - quotient * pow)
+                    }
+                    None => (0, self),
+                }
+            }
+
+            #[inline]
+            fn checked_mul_pow10(self, k: usize) -> Option<$SelfT> {
+                if self == 0 { Some(0) } else { self.checked_mul(pow10_in(k)?) }
+            }
+        }
+
+        forward_ref_shift!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_unsigned_shift! { u64, u128, DIV_POW10_U64_TABLE }
+
+#[cfg(not(feature = "small-tables"))]
+impl_unsigned_shift! {
+    u8, u16, DIV_POW10_U8_TABLE;
+    u16, u32, DIV_POW10_U16_TABLE;
+    u32, u64, DIV_POW10_U32_TABLE
+}
+
+/// Implements `DecimalShift` trait for unsigned integer type narrower than 64 bits, which divides
+/// the value converted to `u64`, so that only the reciprocals of `u64` are stored
+#[cfg(feature = "small-tables")]
+macro_rules! impl_narrow_shift {
+    ($($SelfT: ty),+) => {$(
+        impl DecimalShift for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn div_pow10(self, k: usize) -> $SelfT {
+                // the quotient isn't greater than the value
+                #[allow(clippy::cast_possible_truncation)]
+                { u64::from(self).div_pow10(k) as $SelfT }
+            }
+
+            #[inline]
+            fn divrem_pow10(self, k: usize) -> ($SelfT, $SelfT) {
+                let (quotient, remainder) = u64::from(self).divrem_pow10(k);
+                // neither the quotient nor the remainder is greater than the value
+                #[allow(clippy::cast_possible_truncation)]
+                { (quotient as $SelfT, remainder as $SelfT) }
+            }
+
+            #[inline]
+            fn checked_mul_pow10(self, k: usize) -> Option<$SelfT> {
+                if self == 0 { Some(0) } else { self.checked_mul(pow10_in(k)?) }
+            }
+        }
+
+        forward_ref_shift!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+#[cfg(feature = "small-tables")]
+impl_narrow_shift! { u8, u16, u32 }
+
+impl DecimalShift for u128 {
+    type Output = u128;
+
+    #[inline]
+    fn div_pow10(self, k: usize) -> u128 {
+        match DIV_POW10_U128_TABLE.get(k) {
+            Some(_) if k == 0 => self,
+            // the shift is greater than 128 for k >= 1
+            Some(&(multiplier, shift)) => mul_hi_u128(self >> k, multiplier) >> (shift - 128),
+            None => 0,
+        }
+    }
+
+    #[inline]
+    fn divrem_pow10(self, k: usize) -> (u128, u128) {
+        match pow10(k) {
+            Some(pow) => {
+                let quotient = self.div_pow10(k);
+                (quotient, self - quotient * pow)
+            }
+            None => (0, self),
+        }
+    }
+
+    #[inline]
+    fn checked_mul_pow10(self, k: usize) -> Option<u128> {
+        if self == 0 { Some(0) } else { self.checked_mul(pow10(k)?) }
+    }
+}
+
+forward_ref_shift!(u128; &u128, &mut u128, Box<u128>);
+
+/// `usize` uses the implementation of the unsigned type of the same width, selected by `TargetPtrWidth`
+impl DecimalShift for usize {
+    type Output = usize;
+
+    #[inline]
+    fn div_pow10(self, k: usize) -> usize {
+        // the quotient isn't greater than the value
+        #[allow(clippy::cast_possible_truncation)]
+        { TargetPtrWidth::unsigned(self).div_pow10(k) as usize }
+    }
+
+    #[inline]
+    fn divrem_pow10(self, k: usize) -> (usize, usize) {
+        let (quotient, remainder) = TargetPtrWidth::unsigned(self).divrem_pow10(k);
+        #[allow(clippy::cast_possible_truncation)]
+        { (quotient as usize, remainder as usize) }
+    }
+
+    #[inline]
+    fn checked_mul_pow10(self, k: usize) -> Option<usize> {
+        #[allow(clippy::cast_possible_truncation)]
+        TargetPtrWidth::unsigned(self).checked_mul_pow10(k).map(|product| product as usize)
+    }
+}
+
+forward_ref_shift!(usize; &usize, &mut usize, Box<usize>);
+
+/// Implements `DecimalShift` trait for signed integer type, which divides the magnitude
+macro_rules! impl_signed_shift {
+    ($($SelfT: ty),+) => {$(
+        impl DecimalShift for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn div_pow10(self, k: usize) -> $SelfT {
+                // the quotient only wraps for the minimum value and k = 0
+                #[allow(clippy::cast_possible_wrap)]
+                let quotient = self.abs_diff(0).div_pow10(k) as $SelfT;
+                if self < 0 { quotient.wrapping_neg() } else { quotient }
+            }
+
+            #[inline]
+            fn divrem_pow10(self, k: usize) -> ($SelfT, $SelfT) {
+                let (quotient, remainder) = self.abs_diff(0).divrem_pow10(k);
+                // the magnitudes only wrap for the minimum value, which is restored by the negation
+                #[allow(clippy::cast_possible_wrap)]
+                let (quotient, remainder) = (quotient as $SelfT, remainder as $SelfT);
+                if self < 0 { (quotient.wrapping_neg(), remainder.wrapping_neg()) } else { (quotient, remainder) }
+            }
+
+            #[inline]
+            fn checked_mul_pow10(self, k: usize) -> Option<$SelfT> {
+                if self == 0 { Some(0) } else { self.checked_mul(pow10_in(k)?) }
+            }
+        }
+
+        forward_ref_shift!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_signed_shift! { i8, i16, i32, i64, i128, isize }
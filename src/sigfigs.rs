@@ -0,0 +1,132 @@
+// Copyright 2022 Redglyph
+//
+// Rounding of integers to a number of significant figures
+
+use crate::{pow10, Log10, Rounding};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that rounds integers to a number of significant decimal figures, exactly.
+///
+/// The number of dropped digits is computed from [`log10`](Log10::log10), then the value is divided
+/// by the corresponding power of ten with the selected [`Rounding`] mode, and multiplied back.
+///
+/// # Examples
+/// ```
+/// use ilog::{Rounding, SignificantFigures};
+///
+/// assert_eq!(123456_u32.round_sig_figs(3, Rounding::HalfUp), Some(123000));
+/// assert_eq!((-123556_i32).round_sig_figs(3, Rounding::Floor), Some(-124000));
+/// assert_eq!(987654_u32.truncate_sig_figs(2), 980000);
+/// assert_eq!(250_u8.round_sig_figs(1, Rounding::Ceil), None);   // 300 doesn't fit
+/// ```
+pub trait SignificantFigures {
+    /// Type of the rounded values, which is the integer type, also for its references.
+    type Output;
+
+    /// Returns the value rounded to `n` significant figures with the rounding mode `rounding`, or
+    /// `None` if the rounded value overflows the type. Values which have at most `n` digits are
+    /// unchanged.
+    ///
+    /// # Panics
+    /// This function panics if `n` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::{Rounding, SignificantFigures};
+    /// assert_eq!(1250_u16.round_sig_figs(2, Rounding::HalfEven), Some(1200));
+    /// assert_eq!(1250_u16.round_sig_figs(2, Rounding::HalfUp), Some(1300));
+    /// assert_eq!((-1250_i16).round_sig_figs(2, Rounding::Ceil), Some(-1200));
+    /// assert_eq!(i8::MIN.round_sig_figs(1, Rounding::TowardZero), Some(-100));
+    /// assert_eq!(i8::MIN.round_sig_figs(2, Rounding::AwayFromZero), None);   // -130
+    /// ```
+    fn round_sig_figs(self, n: usize, rounding: Rounding) -> Option<Self::Output>;
+
+    /// Returns the value truncated to `n` significant figures, which is rounded toward zero and
+    /// can't overflow.
+    ///
+    /// # Panics
+    /// This function panics if `n` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::SignificantFigures;
+    /// assert_eq!(u64::MAX.truncate_sig_figs(3), 18400000000000000000);
+    /// assert_eq!((-1999_i32).truncate_sig_figs(1), -1000);
+    /// assert_eq!(42_u8.truncate_sig_figs(5), 42);
+    /// ```
+    #[must_use]
+    fn truncate_sig_figs(self, n: usize) -> Self::Output;
+}
+
+/// Rounds `magnitude` to `n` significant figures, knowing the sign of the value. Returns the
+/// rounded magnitude, or `None` if it overflows a `u128`.
+#[inline]
+fn round_magnitude(magnitude: u128, negative: bool, n: usize, rounding: Rounding) -> Option<u128> {
+    assert!(n > 0, "the number of significant figures must be positive");
+    match magnitude.checked_log10() {
+        Some(log) if log >= n => {
+            // 10^(log + 1 - n) <= magnitude, so it fits
+            let pow = pow10(log + 1 - n).unwrap_or(u128::MAX);
+            rounding.div(magnitude, pow, negative).checked_mul(pow)
+        }
+        _ => Some(magnitude),
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `SignificantFigures` trait to references
+macro_rules! forward_ref_sig_figs {
+    ($SelfT: ty; $( $t:ty ),+) => {$(
+        impl SignificantFigures for $t {
+            type Output = $SelfT;
+
+            #[inline]
+            fn round_sig_figs(self, n: usize, rounding: Rounding) -> Option<$SelfT> {
+                SignificantFigures::round_sig_figs(*self, n, rounding)
+            }
+            #[inline]
+            fn truncate_sig_figs(self, n: usize) -> $SelfT {
+                SignificantFigures::truncate_sig_figs(*self, n)
+            }
+        }
+    )+}
+}
+
+/// Implements `SignificantFigures` trait for integer type, with the unsigned type of the same width
+macro_rules! impl_sig_figs {
+    ($($SelfT: ty, $UnsignedT: ty);+) => {$(
+        impl SignificantFigures for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn round_sig_figs(self, n: usize, rounding: Rounding) -> Option<Self> {
+                #[allow(unused_comparisons)]
+                let negative = self < 0;
+                #[allow(clippy::cast_lossless)]
+                let rounded = round_magnitude(self.abs_diff(0) as u128, negative, n, rounding)?;
+                let max = if negative { <$SelfT>::MIN.abs_diff(0) } else { <$SelfT>::MAX.abs_diff(0) };
+                let rounded = <$UnsignedT>::try_from(rounded).ok().filter(|&r| r <= max)?;
+                // the magnitude is in range, so the conversion only wraps for the minimum value
+                #[allow(clippy::cast_possible_wrap)]
+                let rounded = rounded as $SelfT;
+                Some(if negative { rounded.wrapping_neg() } else { rounded })
+            }
+
+            #[inline]
+            fn truncate_sig_figs(self, n: usize) -> Self {
+                // rounding toward zero can't increase the magnitude
+                self.round_sig_figs(n, Rounding::TowardZero).unwrap_or(self)
+            }
+        }
+
+        forward_ref_sig_figs!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_sig_figs! {
+    u8, u8; i8, u8; u16, u16; i16, u16; u32, u32; i32, u32;
+    u64, u64; i64, u64; u128, u128; i128, u128; usize, usize; isize, usize
+}
@@ -4,7 +4,13 @@
 
 #![cfg(test)]
 
-use crate::IntLog;
+use crate::{Alphabet, Checked, DecimalFormat, DecimalShift, Engineering, GroupPattern, Grouped, HumanDuration, Humanize, ILog, LeadingDigits, Log10, Log2, LogIterator, LogProduct, LogWith, Panic, PtrWidth, PtrWidthInt, RadixFormat, Rescale, RoundLog, Rounding, Saturate, Scientific, SignificantFigures, Valuation, Wrapping, ZeroForNonPositive};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
 
 // ---------------------------------------------------------------------------------------------
 
@@ -103,3 +109,1437 @@ test_log! { log10_u128, log2_u128,  u128, 37, 9999999999999999999999999999999999
 test_log! { log10_i128, log2_i128,  i128, 37, 99999999999999999999999999999999999999_u128, 37, 38, 126, -1 }
 
 // ---------------------------------------------------------------------------------------------
+
+/// Unit tests of the base 10 logarithm estimates for signed and unsigned types
+macro_rules! test_log_estimate {
+    (
+        $Name: ident,           // test name
+        $SelfT: ty,             // type to test
+        $Msb: expr              // MSB index of type
+    ) => {
+        #[test]
+        fn $Name() {
+            fn check(value: $SelfT) {
+                let log10 = value.log10();
+                let estimate = value.log10_estimate();
+                let upper = value.log10_upper_bound();
+                assert!(estimate == log10 || estimate + 1 == log10, "estimate of {value}: {estimate} (log10 = {log10})");
+                assert!(upper == log10 || upper == log10 + 1, "upper bound of {value}: {upper} (log10 = {log10})");
+            }
+            // tests the lowest and highest values of each bit position
+            for i in 0..=$Msb {
+                let low: $SelfT = 1 << i;
+                check(low);
+                check(low | (low - 1));
+            }
+            // tests powers of 10
+            let mut value: $SelfT = 1;
+            while let Some(next) = value.checked_mul(10) {
+                value = next;
+                check(value - 1);
+                check(value);
+            }
+        }
+    }
+}
+
+test_log_estimate! { log10_estimate_u8, u8, 7 }
+test_log_estimate! { log10_estimate_i8, i8, 6 }
+test_log_estimate! { log10_estimate_u16, u16, 15 }
+test_log_estimate! { log10_estimate_i16, i16, 14 }
+test_log_estimate! { log10_estimate_u32, u32, 31 }
+test_log_estimate! { log10_estimate_i32, i32, 30 }
+test_log_estimate! { log10_estimate_u64, u64, 63 }
+test_log_estimate! { log10_estimate_i64, i64, 62 }
+test_log_estimate! { log10_estimate_u128, u128, 127 }
+test_log_estimate! { log10_estimate_i128, i128, 126 }
+test_log_estimate! { log10_estimate_usize, usize, usize::BITS - 1 }
+test_log_estimate! { log10_estimate_isize, isize, isize::BITS - 2 }
+
+// ---------------------------------------------------------------------------------------------
+// Differential tests against the `core` logarithms
+
+/// Defines a function that compares all the `IntLog` methods with the `core` logarithms for a value,
+/// and the same function for the reference, mutable reference and `Box` forms
+macro_rules! check_core_log {
+    ($Name: ident, $NameFwd: ident, $SelfT: ty) => {
+        fn $Name(value: $SelfT) {
+            assert_eq!(value.checked_log2(), value.checked_ilog2().map(|x| x as usize), "checked_log2({value})");
+            assert_eq!(value.checked_log10(), value.checked_ilog10().map(|x| x as usize), "checked_log10({value})");
+            assert_eq!(ILog::checked_ilog2(value), value.checked_ilog2(), "ILog::checked_ilog2({value})");
+            assert_eq!(ILog::checked_ilog10(value), value.checked_ilog10(), "ILog::checked_ilog10({value})");
+            if value > 0 {
+                assert_eq!(value.log2(), value.ilog2() as usize, "log2({value})");
+                assert_eq!(value.log10(), value.ilog10() as usize, "log10({value})");
+                assert_eq!(ILog::ilog2(value), value.ilog2(), "ILog::ilog2({value})");
+                assert_eq!(ILog::ilog10(value), value.ilog10(), "ILog::ilog10({value})");
+            }
+        }
+
+        fn $NameFwd(mut value: $SelfT) {
+            let expected = (value.checked_ilog2().map(|x| x as usize), value.checked_ilog10().map(|x| x as usize));
+            assert_eq!(((&value).checked_log2(), (&value).checked_log10()), expected, "&{value}");
+            assert_eq!(((&mut value).checked_log2(), (&mut value).checked_log10()), expected, "&mut {value}");
+            assert_eq!((Box::new(value).checked_log2(), Box::new(value).checked_log10()), expected, "Box({value})");
+            if value > 0 {
+                let expected = (expected.0.unwrap(), expected.1.unwrap());
+                assert_eq!(((&value).log2(), (&value).log10()), expected, "&{value}");
+                assert_eq!(((&mut value).log2(), (&mut value).log10()), expected, "&mut {value}");
+                assert_eq!((Box::new(value).log2(), Box::new(value).log10()), expected, "Box({value})");
+            }
+            for base in [2, 3, 7, 10, 16, 36, 100, <$SelfT>::MAX] {
+                let expected = value.checked_ilog(base);
+                assert_eq!(ILog::checked_ilog(value, base), expected, "ILog::checked_ilog({value}, {base})");
+                assert_eq!(ILog::checked_ilog(&value, &base), expected, "ILog::checked_ilog(&{value}, &{base})");
+                assert_eq!(ILog::checked_ilog(Box::new(value), Box::new(base)), expected, "ILog::checked_ilog(Box({value}), Box({base}))");
+                if let Some(expected) = expected {
+                    assert_eq!(ILog::ilog(value, base), expected, "ILog::ilog({value}, {base})");
+                    let mut base_mut = base;
+                    assert_eq!(ILog::ilog(&mut value, &mut base_mut), expected, "ILog::ilog(&mut {value}, &mut {base})");
+                }
+            }
+            let expected = (value.checked_ilog2(), value.checked_ilog10());
+            assert_eq!((ILog::checked_ilog2(&value), ILog::checked_ilog10(&value)), expected, "ILog &{value}");
+            assert_eq!((ILog::checked_ilog2(&mut value), ILog::checked_ilog10(&mut value)), expected, "ILog &mut {value}");
+            assert_eq!((ILog::checked_ilog2(Box::new(value)), ILog::checked_ilog10(Box::new(value))), expected, "ILog Box({value})");
+            if value > 0 {
+                let expected = (value.ilog2(), value.ilog10());
+                assert_eq!((ILog::ilog2(&value), ILog::ilog10(&mut value)), expected, "ILog &{value}");
+                assert_eq!((ILog::ilog2(Box::new(value)), ILog::ilog10(Box::new(value))), expected, "ILog Box({value})");
+            }
+            assert_eq!(ILog::checked_ilog(value, 1), None, "ILog::checked_ilog({value}, 1)");
+            assert_eq!(ILog::checked_ilog(value, 0), None, "ILog::checked_ilog({value}, 0)");
+        }
+    }
+}
+
+check_core_log!(check_core_u8, check_core_fwd_u8, u8);
+check_core_log!(check_core_i8, check_core_fwd_i8, i8);
+check_core_log!(check_core_u16, check_core_fwd_u16, u16);
+check_core_log!(check_core_i16, check_core_fwd_i16, i16);
+check_core_log!(check_core_u32, check_core_fwd_u32, u32);
+check_core_log!(check_core_i32, check_core_fwd_i32, i32);
+check_core_log!(check_core_u64, check_core_fwd_u64, u64);
+check_core_log!(check_core_i64, check_core_fwd_i64, i64);
+check_core_log!(check_core_u128, check_core_fwd_u128, u128);
+check_core_log!(check_core_i128, check_core_fwd_i128, i128);
+check_core_log!(check_core_usize, check_core_fwd_usize, usize);
+check_core_log!(check_core_isize, check_core_fwd_isize, isize);
+
+/// Exhaustive tests against the `core` logarithms
+macro_rules! test_core_exhaustive {
+    ($Name: ident, $SelfT: ty, $Check: ident, $CheckFwd: ident) => {
+        #[test]
+        fn $Name() {
+            for value in <$SelfT>::MIN..=<$SelfT>::MAX {
+                $Check(value);
+                $CheckFwd(value);
+            }
+        }
+    }
+}
+
+test_core_exhaustive!(core_exhaustive_u8, u8, check_core_u8, check_core_fwd_u8);
+test_core_exhaustive!(core_exhaustive_i8, i8, check_core_i8, check_core_fwd_i8);
+test_core_exhaustive!(core_exhaustive_u16, u16, check_core_u16, check_core_fwd_u16);
+test_core_exhaustive!(core_exhaustive_i16, i16, check_core_i16, check_core_fwd_i16);
+
+/// Exhaustive test of `u32` against the `core` logarithms in release builds, or a test of one value
+/// every 65521 (largest prime < 2^16) in debug builds. The forms are only tested on the sampled values.
+#[test]
+fn core_exhaustive_u32() {
+    const STEP: usize = 65521;
+    #[cfg(not(debug_assertions))]
+    for value in 0..=u32::MAX {
+        check_core_u32(value);
+    }
+    for value in (0..=u32::MAX).step_by(STEP).chain([u32::MAX]) {
+        check_core_u32(value);
+        check_core_fwd_u32(value);
+    }
+    for value in (i32::MIN..=i32::MAX).step_by(STEP).chain([i32::MAX]) {
+        check_core_i32(value);
+        check_core_fwd_i32(value);
+    }
+}
+
+/// Simple xorshift* pseudo-random generator, to get reproducible tests without dependency
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+    }
+}
+
+/// Calls `$Check(value, rng)` for sample values of `$SelfT`: all the values if the type has at most
+/// `$ExhaustiveBits` bits, or else `$Iterations` random values shifted right by a random amount, so
+/// that all the magnitudes are equally represented, and the powers of ten with their neighbours and
+/// opposites. The extreme values are always included.
+macro_rules! for_each_sample {
+    ($SelfT: ty, $ExhaustiveBits: expr, $Iterations: expr, $Seed: expr, $Check: expr) => {{
+        let mut rng = Rng($Seed);
+        let check = $Check;
+        if <$SelfT>::BITS <= $ExhaustiveBits {
+            for value in <$SelfT>::MIN..=<$SelfT>::MAX {
+                check(value, &mut rng);
+            }
+        } else {
+            for _ in 0..$Iterations {
+                let shift = rng.next_u64() % u64::from(<$SelfT>::BITS);
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let value = (rng.next_u128() as $SelfT) >> shift;
+                check(value, &mut rng);
+            }
+            for power in core::iter::successors(Some(1 as $SelfT), |p| p.checked_mul(10)) {
+                for value in [power - 1, power, power + 1] {
+                    check(value, &mut rng);
+                    check(value.wrapping_neg(), &mut rng);
+                }
+            }
+        }
+        for value in [<$SelfT>::MIN, <$SelfT>::MIN + 1, 0, 1, <$SelfT>::MAX - 1, <$SelfT>::MAX] {
+            check(value, &mut rng);
+        }
+    }}
+}
+
+/// Extra values of `test_samples!` when there are none
+macro_rules! no_extra_samples {
+    ($SelfT: ty) => { [] as [$SelfT; 0] }
+}
+
+/// Defines the module `$Name` with one test per integer type, which runs `$Check!(type, value, rng)`
+/// for the values of `for_each_sample!`, then for the values of the optional `$Extra!(type)`
+macro_rules! test_samples {
+    ($Name: ident, $Check: ident, $ExhaustiveBits: expr, $Iterations: expr, $Seed: expr) => {
+        test_samples!($Name, $Check, $ExhaustiveBits, $Iterations, $Seed, no_extra_samples);
+    };
+    ($Name: ident, $Check: ident, $ExhaustiveBits: expr, $Iterations: expr, $Seed: expr, $Extra: ident) => {
+        mod $Name {
+            use super::*;
+
+            test_samples!(@types $Check, $ExhaustiveBits, $Iterations, $Seed, $Extra;
+                u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+        }
+    };
+    (@types $Check: ident, $ExhaustiveBits: expr, $Iterations: expr, $Seed: expr, $Extra: ident; $($T: ident),+) => {$(
+        #[test]
+        #[allow(unused_variables)]  // the random generator is only used by some checks
+        fn $T() {
+            let check = |value: $T, rng: &mut Rng| $Check!($T, value, rng);
+            for_each_sample!($T, $ExhaustiveBits, $Iterations, $Seed, check);
+            let rng = &mut Rng($Seed);
+            for value in $Extra!($T) {
+                check(value, rng);
+            }
+        }
+    )+};
+}
+
+/// Randomized tests against the `core` logarithms. The random values are shifted right by a random
+/// amount so that all the magnitudes are equally represented.
+macro_rules! test_core_random {
+    ($Name: ident, $SelfT: ty, $Check: ident, $CheckFwd: ident) => {
+        #[test]
+        fn $Name() {
+            const ITERATIONS: usize = 100_000;
+            let mut rng = Rng(0x9E3779B97F4A7C15);
+            for _ in 0..ITERATIONS {
+                let shift = rng.next_u64() % u64::from(<$SelfT>::BITS);
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let value = (rng.next_u128() as $SelfT) >> shift;
+                $Check(value);
+                $CheckFwd(value);
+            }
+            for value in [<$SelfT>::MIN, <$SelfT>::MIN + 1, 0, 1, <$SelfT>::MAX - 1, <$SelfT>::MAX] {
+                $Check(value);
+                $CheckFwd(value);
+            }
+        }
+    }
+}
+
+test_core_random!(core_random_u64, u64, check_core_u64, check_core_fwd_u64);
+test_core_random!(core_random_i64, i64, check_core_i64, check_core_fwd_i64);
+test_core_random!(core_random_u128, u128, check_core_u128, check_core_fwd_u128);
+test_core_random!(core_random_i128, i128, check_core_i128, check_core_fwd_i128);
+test_core_random!(core_random_usize, usize, check_core_usize, check_core_fwd_usize);
+test_core_random!(core_random_isize, isize, check_core_isize, check_core_fwd_isize);
+
+// ---------------------------------------------------------------------------------------------
+// `usize` and `isize` for all the pointer widths
+
+/// Tests the `usize` and `isize` implementations for a pointer width of `BITS` bits, which may
+/// differ from the host's. Only the values that fit in the host's `usize` can be tested.
+fn check_ptr_width<const BITS: u32>() where PtrWidth<BITS>: PtrWidthInt, <PtrWidth<BITS> as PtrWidthInt>::Unsigned: PartialEq + core::fmt::Debug {
+    let bits = BITS.min(usize::BITS);
+    let max = usize::MAX >> (usize::BITS - bits);
+    let mut values = (0..bits).flat_map(|i| [1_usize << i, (1_usize << i) - 1, max >> i]).collect::<Vec<_>>();
+    values.extend(core::iter::successors(Some(1_usize), |v| v.checked_mul(10)).take_while(|&v| v <= max)
+        .flat_map(|v| [v - 1, v, v + 1]));
+    for value in values {
+        let expected = (value.checked_ilog2().map(|x| x as usize), value.checked_ilog10().map(|x| x as usize));
+        let unsigned = PtrWidth::<BITS>::unsigned(value);
+        assert_eq!((unsigned.checked_log2(), unsigned.checked_log10()), expected, "PtrWidth<{BITS}>::unsigned({value})");
+        if value > 0 {
+            assert_eq!((unsigned.log2(), unsigned.log10()), (expected.0.unwrap(), expected.1.unwrap()), "PtrWidth<{BITS}>::unsigned({value})");
+            let wide = u128::try_from(value).unwrap();
+            let expected_round = (round_log_reference(wide, 2), round_log_reference(wide, 10));
+            assert_eq!((unsigned.round_log2(), unsigned.round_log10()), expected_round, "PtrWidth<{BITS}>::unsigned({value}) rounded");
+            for k in [0, 1, 4, 9, 19, 20] {
+                let pow = crate::pow10(k).and_then(|pow| usize::try_from(pow).ok()).filter(|&pow| pow <= max);
+                let expected_shift = pow.map_or((0, value), |pow| (value / pow, value % pow));
+                let expected_shift = (PtrWidth::<BITS>::unsigned(expected_shift.0), PtrWidth::<BITS>::unsigned(expected_shift.1));
+                assert_eq!(unsigned.divrem_pow10(k), expected_shift, "PtrWidth<{BITS}>::unsigned({value}).divrem_pow10({k})");
+            }
+        }
+        if value <= max >> 1 {
+            let value = isize::try_from(value).unwrap();
+            let signed = PtrWidth::<BITS>::signed(value);
+            assert_eq!((signed.checked_log2(), signed.checked_log10()), expected, "PtrWidth<{BITS}>::signed({value})");
+            let signed = PtrWidth::<BITS>::signed(-value);
+            assert_eq!((signed.checked_log2(), signed.checked_log10()), (None, None), "PtrWidth<{BITS}>::signed({})", -value);
+        }
+    }
+}
+
+#[test]
+fn log_ptr_width_16() {
+    check_ptr_width::<16>();
+}
+
+#[test]
+fn log_ptr_width_32() {
+    check_ptr_width::<32>();
+}
+
+#[test]
+fn log_ptr_width_64() {
+    check_ptr_width::<64>();
+}
+
+// ---------------------------------------------------------------------------------------------
+// Associated constants
+
+/// Unit tests of the associated constants, compared with the logarithms and the formatted
+/// extreme values of the type
+macro_rules! test_log_consts {
+    ($Name: ident, $SelfT: ty) => {
+        #[test]
+        fn $Name() {
+            let max_len = format!("{}", <$SelfT>::MIN).len().max(format!("{}", <$SelfT>::MAX).len());
+            let max_digits = format!("{}", <$SelfT>::MIN.abs_diff(0)).len().max(format!("{}", <$SelfT>::MAX).len());
+            assert_eq!(<$SelfT>::MAX_LOG2, <$SelfT>::MAX.log2(), "MAX_LOG2");
+            assert_eq!(<$SelfT>::MAX_LOG10, <$SelfT>::MAX.log10(), "MAX_LOG10");
+            assert_eq!(<$SelfT>::MAX_DECIMAL_DIGITS, max_digits, "MAX_DECIMAL_DIGITS");
+            assert_eq!(<$SelfT>::MAX_DECIMAL_LEN, max_len, "MAX_DECIMAL_LEN");
+            assert_eq!(<&$SelfT>::MAX_LOG10, <$SelfT>::MAX_LOG10, "&MAX_LOG10");
+            assert_eq!(<&mut $SelfT>::MAX_DECIMAL_LEN, <$SelfT>::MAX_DECIMAL_LEN, "&mut MAX_DECIMAL_LEN");
+            assert_eq!(<Box<$SelfT>>::MAX_LOG2, <$SelfT>::MAX_LOG2, "Box MAX_LOG2");
+        }
+    }
+}
+
+test_log_consts! { log_consts_u8, u8 }
+test_log_consts! { log_consts_i8, i8 }
+test_log_consts! { log_consts_u16, u16 }
+test_log_consts! { log_consts_i16, i16 }
+test_log_consts! { log_consts_u32, u32 }
+test_log_consts! { log_consts_i32, i32 }
+test_log_consts! { log_consts_u64, u64 }
+test_log_consts! { log_consts_i64, i64 }
+test_log_consts! { log_consts_u128, u128 }
+test_log_consts! { log_consts_i128, i128 }
+test_log_consts! { log_consts_usize, usize }
+test_log_consts! { log_consts_isize, isize }
+
+// ---------------------------------------------------------------------------------------------
+// Panics of the `core`-compatible logarithms
+
+#[test]
+#[should_panic(expected = "argument of integer logarithm must be positive")]
+fn ilog10_zero_panics() {
+    ILog::ilog10(0_u32);
+}
+
+#[test]
+#[should_panic(expected = "argument of integer logarithm must be positive")]
+fn ilog2_negative_panics() {
+    ILog::ilog2(-1_i64);
+}
+
+#[test]
+#[should_panic(expected = "base of integer logarithm must be at least 2")]
+fn ilog_base_panics() {
+    ILog::ilog(100_u8, 1);
+}
+
+// ---------------------------------------------------------------------------------------------
+// Logarithms with a policy
+
+/// Unit tests of the logarithms with a policy; `$Negative` is a negative value and `$Wrapped`
+/// its unsigned reinterpretation, or any positive value for unsigned types
+macro_rules! test_log_with {
+    ($Name: ident, $SelfT: ty, $Negative: expr, $Wrapped: expr) => {
+        #[test]
+        fn $Name() {
+            for value in [1, 9, 10, 64, <$SelfT>::MAX] {
+                let (log2, log10) = (value.log2(), value.log10());
+                assert_eq!(value.log2_with::<Panic>(), log2, "Panic log2({value})");
+                assert_eq!(value.log10_with::<Panic>(), log10, "Panic log10({value})");
+                assert_eq!(value.log2_with::<ZeroForNonPositive>(), log2, "ZeroForNonPositive log2({value})");
+                assert_eq!(value.log10_with::<Saturate>(), log10, "Saturate log10({value})");
+                assert_eq!(value.log2_with::<Checked>(), Some(log2), "Checked log2({value})");
+                assert_eq!(value.log10_with::<Checked>(), Some(log10), "Checked log10({value})");
+                assert_eq!(value.log2_with::<Wrapping>(), log2, "Wrapping log2({value})");
+                assert_eq!(value.log10_with::<Wrapping>(), log10, "Wrapping log10({value})");
+                assert_eq!((&value).log10_with::<Checked>(), Some(log10), "Checked log10(&{value})");
+                assert_eq!(Box::new(value).log2_with::<Wrapping>(), log2, "Wrapping log2(Box({value}))");
+            }
+            let zero: $SelfT = 0;
+            assert_eq!(zero.log2_with::<ZeroForNonPositive>(), 0, "ZeroForNonPositive log2(0)");
+            assert_eq!(zero.log10_with::<ZeroForNonPositive>(), 0, "ZeroForNonPositive log10(0)");
+            assert_eq!(zero.log2_with::<Checked>(), None, "Checked log2(0)");
+            assert_eq!(zero.log10_with::<Checked>(), None, "Checked log10(0)");
+            assert_eq!(zero.log2_with::<Wrapping>(), 0, "Wrapping log2(0)");
+            assert_eq!(zero.log10_with::<Wrapping>(), 0, "Wrapping log10(0)");
+            if <$SelfT>::MIN != 0 {
+                let negative: $SelfT = $Negative;
+                assert_eq!(negative.log2_with::<Saturate>(), 0, "Saturate log2({negative})");
+                assert_eq!(negative.log10_with::<ZeroForNonPositive>(), 0, "ZeroForNonPositive log10({negative})");
+                assert_eq!(negative.log2_with::<Checked>(), None, "Checked log2({negative})");
+                assert_eq!((&mut { negative }).log10_with::<Checked>(), None, "Checked log10(&mut {negative})");
+                assert_eq!(negative.log2_with::<Wrapping>(), $Wrapped.log2(), "Wrapping log2({negative})");
+                assert_eq!(negative.log10_with::<Wrapping>(), $Wrapped.log10(), "Wrapping log10({negative})");
+            }
+        }
+    }
+}
+
+test_log_with! { log_with_u8, u8, 1, 1_u8 }
+test_log_with! { log_with_i8, i8, -1, u8::MAX }
+test_log_with! { log_with_u16, u16, 1, 1_u16 }
+test_log_with! { log_with_i16, i16, -1000, 64536_u16 }
+test_log_with! { log_with_u32, u32, 1, 1_u32 }
+test_log_with! { log_with_i32, i32, i32::MIN, 1_u32 << 31 }
+test_log_with! { log_with_u64, u64, 1, 1_u64 }
+test_log_with! { log_with_i64, i64, -1, u64::MAX }
+test_log_with! { log_with_u128, u128, 1, 1_u128 }
+test_log_with! { log_with_i128, i128, -2, u128::MAX - 1 }
+test_log_with! { log_with_usize, usize, 1, 1_usize }
+test_log_with! { log_with_isize, isize, -1, usize::MAX }
+
+#[test]
+#[should_panic(expected = "argument of integer logarithm must be positive")]
+fn log10_with_panic() {
+    (-5_i16).log10_with::<Panic>();
+}
+
+#[test]
+#[should_panic(expected = "argument of integer logarithm must be positive")]
+fn log2_with_panic() {
+    0_u64.log2_with::<Panic>();
+}
+
+// ---------------------------------------------------------------------------------------------
+// Decimal formatting
+
+/// Compares the decimal formatting of a value and of its references with `format!`
+macro_rules! check_decimal {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        let expected = format!("{value}");
+        let mut buffer = [0_u8; 48];
+        let len = value.write_decimal(&mut buffer);
+        assert_eq!(&buffer[..len], expected.as_bytes(), "write_decimal({value})");
+        assert_eq!(value.decimal_len(), expected.len(), "decimal_len({value})");
+        let (array, len) = value.to_decimal_array();
+        assert_eq!(&array[..len], expected.as_bytes(), "to_decimal_array({value})");
+        assert!(array[len..].iter().all(|&b| b == 0), "to_decimal_array({value})");
+        let mut exact = vec![0_u8; expected.len()];
+        assert_eq!(value.write_decimal(&mut exact), expected.len(), "write_decimal({value}) in exact buffer");
+        assert_eq!((&value).decimal_len(), expected.len(), "decimal_len(&{value})");
+        assert_eq!((&mut { value }).write_decimal(&mut exact), expected.len(), "write_decimal(&mut {value})");
+        assert_eq!(Box::new(value).to_decimal_array(), (array, len), "to_decimal_array(Box({value}))");
+    }}
+}
+
+test_samples!(decimal, check_decimal, 16, 20_000, 0x2545F4914F6CDD1D);
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn decimal_short_buffer_panics() {
+    let mut buffer = [0_u8; 3];
+    (-100_i32).write_decimal(&mut buffer);
+}
+
+// ---------------------------------------------------------------------------------------------
+// Radix formatting
+
+/// Reference implementation of the radix formatting, with divisions on `u128`
+fn radix_reference(negative: bool, mut magnitude: u128, alphabet: Alphabet) -> Vec<u8> {
+    let radix = u128::from(alphabet.radix());
+    let mut digits = Vec::new();
+    loop {
+        digits.push(alphabet.digits()[usize::try_from(magnitude % radix).unwrap()]);
+        magnitude /= radix;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if negative {
+        digits.push(b'-');
+    }
+    digits.reverse();
+    digits
+}
+
+/// Alphabets tested with the reference implementation
+const RADIX_ALPHABETS: [Alphabet<'static>; 12] = {
+    const ALL_BYTES: [u8; 256] = {
+        let mut bytes = [0; 256];
+        let mut i: u8 = 0;
+        while i < 255 {
+            i += 1;
+            bytes[i as usize] = i;
+        }
+        bytes
+    };
+    [
+        Alphabet::BASE36, Alphabet::BASE58, Alphabet::BASE62,
+        Alphabet::base(3).unwrap(), Alphabet::base(7).unwrap(), Alphabet::base(10).unwrap(),
+        Alphabet::base(31).unwrap(), Alphabet::base(32).unwrap(), Alphabet::base(61).unwrap(),
+        Alphabet::new(b"ACGT").unwrap(), Alphabet::new(ALL_BYTES.split_at(255).0).unwrap(), Alphabet::new(&ALL_BYTES).unwrap(),
+    ]
+};
+
+/// Reference length of the representation in radix `radix`, with divisions on `u128`
+fn radix_len_reference(negative: bool, mut magnitude: u128, radix: u128) -> usize {
+    let mut len = 1 + usize::from(negative);
+    while magnitude >= radix {
+        magnitude /= radix;
+        len += 1;
+    }
+    len
+}
+
+/// Compares the radix formatting of a value and of its references with `format!` and with the
+/// reference implementation
+macro_rules! check_radix {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        let sign = if format!("{value}").starts_with('-') { "-" } else { "" };
+        let magnitude = value.abs_diff(0);
+        let formatted = [
+            (Alphabet::BINARY, format!("{sign}{magnitude:b}")),
+            (Alphabet::OCTAL, format!("{sign}{magnitude:o}")),
+            (Alphabet::DECIMAL, format!("{value}")),
+            (Alphabet::HEX_LOWER, format!("{sign}{magnitude:x}")),
+            (Alphabet::HEX_UPPER, format!("{sign}{magnitude:X}")),
+        ];
+        let mut buffer = [0_u8; 130];
+        for (alphabet, expected) in formatted {
+            let len = value.write_radix(alphabet, &mut buffer);
+            assert_eq!(&buffer[..len], expected.as_bytes(), "write_radix({value}, {})", alphabet.radix());
+            assert_eq!(value.radix_len(alphabet.radix()), len, "radix_len({value}, {})", alphabet.radix());
+        }
+        for alphabet in RADIX_ALPHABETS {
+            let expected = radix_reference(!sign.is_empty(), u128::try_from(magnitude).unwrap(), alphabet);
+            let len = value.write_radix(alphabet, &mut buffer);
+            assert_eq!(&buffer[..len], &expected[..], "write_radix({value}, {:?})", alphabet);
+            assert_eq!(value.radix_len(alphabet.radix()), len, "radix_len({value}, {})", alphabet.radix());
+        }
+        for radix in [3, 100, 257, 1000, 65537, u32::MAX] {
+            let expected = radix_len_reference(!sign.is_empty(), u128::try_from(magnitude).unwrap(), u128::from(radix));
+            assert_eq!(value.radix_len(radix), expected, "radix_len({value}, {radix})");
+        }
+        let len = (&value).write_radix(Alphabet::BASE36, &mut buffer);
+        assert_eq!((&mut { value }).radix_len(36), len, "radix_len(&mut {value}, 36)");
+        assert_eq!(Box::new(value).write_radix(Alphabet::BASE36, &mut buffer), len, "write_radix(Box({value}), BASE36)");
+    }}
+}
+
+test_samples!(radix, check_radix, 8, 5_000, 0x853C49E6748FEA9B);
+
+#[test]
+fn radix_alphabets_checks() {
+    assert_eq!(Alphabet::base(1), None);
+    assert_eq!(Alphabet::base(63), None);
+    assert_eq!(Alphabet::base(16), Some(Alphabet::HEX_LOWER));
+    assert_eq!(Alphabet::base(62), Some(Alphabet::BASE62));
+    assert_eq!(Alphabet::new(b"0"), None);
+    assert_eq!(Alphabet::new(b"0120"), None);
+    assert_eq!(Alphabet::BASE58.radix(), 58);
+    assert!(!Alphabet::BASE58.digits().iter().any(|d| b"0OIl".contains(d)));
+}
+
+// ---------------------------------------------------------------------------------------------
+// Digit grouping
+
+/// Reference implementation of the digit grouping, from the output of `format!`
+fn grouped_reference(formatted: &str, separator: char, first: usize, rest: usize) -> String {
+    let (sign, digits) = formatted.split_at(usize::from(formatted.starts_with('-')));
+    let mut groups = Vec::new();
+    let mut end = digits.len();
+    let mut size = first;
+    while end > size {
+        groups.push(&digits[end - size..end]);
+        end -= size;
+        size = rest;
+    }
+    groups.push(&digits[..end]);
+    groups.reverse();
+    format!("{sign}{}", groups.join(&*separator.to_string()))
+}
+
+/// Compares the digit grouping of a value with the reference implementation
+macro_rules! check_grouped {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        let formatted = format!("{value}");
+        for (separator, first, rest) in [(',', 3, 3), ('.', 3, 2), (' ', 4, 4), ('\u{202F}', 3, 2), ('_', 1, 1), ('\'', 2, 5)] {
+            let expected = grouped_reference(&formatted, separator, first, rest);
+            let grouped = Grouped::new(value).separator(separator).pattern(GroupPattern::new(first, rest));
+            assert_eq!(grouped.to_string(), expected, "Grouped({value}, '{separator}', {first}, {rest})");
+            let mut buffer = [0_u8; 200];
+            let len = grouped.write_to(&mut buffer);
+            assert_eq!(&buffer[..len], expected.as_bytes(), "write_to({value}, '{separator}', {first}, {rest})");
+            assert_eq!(grouped.output_len(), expected.len(), "output_len({value}, '{separator}', {first}, {rest})");
+        }
+    }}
+}
+
+test_samples!(grouped, check_grouped, 8, 2_000, 0xDA942042E4DD58B5);
+
+#[test]
+fn grouped_patterns() {
+    assert_eq!(Grouped::new(1_234_567_u32).to_string(), "1,234,567");
+    assert_eq!(Grouped::new(123_456_789_u32).pattern(GroupPattern::INDIAN).to_string(), "12,34,56,789");
+    assert_eq!(Grouped::new(-100_i8).to_string(), "-100");
+    assert_eq!(format!("{:*^11}", Grouped::new(-1000_i16)), "**-1,000***");
+    assert_eq!(format!("{:<7}|", Grouped::new(1234_u16).separator('\u{202F}')), "1\u{202F}234  |");
+    // longest output: a 4-byte separator between all the digits of i128::MIN
+    let grouped = Grouped::new(i128::MIN).separator('\u{1F600}').pattern(GroupPattern::new(1, 1));
+    let expected = format!("-{}", i128::MIN.unsigned_abs().to_string().chars().map(String::from).collect::<Vec<_>>().join("\u{1F600}"));
+    assert_eq!(grouped.output_len(), 192);
+    assert_eq!(grouped.to_string(), expected);
+}
+
+// ---------------------------------------------------------------------------------------------
+// Scientific and engineering notations
+
+const ROUNDINGS: [Rounding; 6] = [Rounding::HalfEven, Rounding::HalfUp, Rounding::Floor, Rounding::Ceil, Rounding::TowardZero, Rounding::AwayFromZero];
+
+/// Reference implementation of the notations, which rounds the digits in the output of `format!`
+fn notation_reference(formatted: &str, digits: usize, rounding: Rounding, step: usize) -> String {
+    let negative = formatted.starts_with('-');
+    let mut decimal = formatted.trim_start_matches('-').as_bytes().to_vec();
+    let mut exp = decimal.len() - 1;
+    if decimal.len() > digits {
+        let dropped = decimal.split_off(digits);
+        let tie = dropped[0] == b'5' && dropped[1..].iter().all(|&d| d == b'0');
+        let nonzero = dropped.iter().any(|&d| d != b'0');
+        let up = match rounding {
+            Rounding::HalfEven => dropped[0] > b'5' || (dropped[0] == b'5' && !tie) || (tie && (decimal[digits - 1] - b'0') % 2 == 1),
+            Rounding::HalfUp => dropped[0] >= b'5',
+            Rounding::Floor => negative && nonzero,
+            Rounding::Ceil => !negative && nonzero,
+            Rounding::TowardZero => false,
+            Rounding::AwayFromZero => nonzero,
+        };
+        if up {
+            // increments the digits with carry
+            let mut index = digits;
+            loop {
+                if index == 0 {
+                    decimal.insert(0, b'1');
+                    decimal.pop();
+                    exp += 1;
+                    break;
+                }
+                index -= 1;
+                if decimal[index] == b'9' {
+                    decimal[index] = b'0';
+                } else {
+                    decimal[index] += 1;
+                    break;
+                }
+            }
+        }
+    }
+    let int_len = 1 + exp % step;
+    decimal.resize(digits.max(int_len), b'0');
+    let (int, frac) = decimal.split_at(int_len);
+    let int = core::str::from_utf8(int).unwrap();
+    let frac = core::str::from_utf8(frac).unwrap();
+    let sign = if negative { "-" } else { "" };
+    let point = if frac.is_empty() { "" } else { "." };
+    format!("{sign}{int}{point}{frac}e{}", exp - exp % step)
+}
+
+/// Compares the notations of a value with the reference implementation, for a few numbers of
+/// significant digits and a random one
+macro_rules! check_notation {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        let formatted = format!("{value}");
+        #[allow(clippy::cast_possible_truncation)]
+        let random_digits = (1 + ($rng.next_u64() % (<$SelfT>::MAX_DECIMAL_DIGITS as u64 + 2)) as usize).min(39);
+        for digits in [1, 2, 3, 4, random_digits] {
+            for rounding in ROUNDINGS {
+                let scientific = Scientific::new(value).significant_digits(digits).rounding(rounding).to_string();
+                assert_eq!(scientific, notation_reference(&formatted, digits, rounding, 1), "Scientific({value}, {digits}, {rounding:?})");
+                let engineering = Engineering::new(value).significant_digits(digits).rounding(rounding).to_string();
+                assert_eq!(engineering, notation_reference(&formatted, digits, rounding, 3), "Engineering({value}, {digits}, {rounding:?})");
+            }
+            // the `core` exponential notation rounds the ties to even
+            assert_eq!(Scientific::new(value).significant_digits(digits).to_string(), format!("{value:.*e}", digits - 1), "Scientific({value}, {digits})");
+        }
+    }}
+}
+
+test_samples!(notation, check_notation, 8, 1_000, 0x2545F4914F6CDD1D);
+
+#[test]
+fn notation_rounding_carry() {
+    assert_eq!(Scientific::new(999_u16).significant_digits(2).to_string(), "1.0e3");
+    assert_eq!(Engineering::new(999_499_u32).significant_digits(3).to_string(), "999e3");
+    assert_eq!(Engineering::new(999_500_u32).significant_digits(3).to_string(), "1.00e6");
+    assert_eq!(Engineering::new(-1_i8).rounding(Rounding::AwayFromZero).to_string(), "-1.00e0");
+    assert_eq!(Scientific::new(u128::MAX).significant_digits(1).rounding(Rounding::Ceil).to_string(), "4e38");
+    assert_eq!(Scientific::new(i128::MIN).significant_digits(1).rounding(Rounding::Ceil).to_string(), "-1e38");
+    assert_eq!(format!("{:<8}|", Engineering::new(0_u8).significant_digits(2)), "0.0e0   |");
+}
+
+#[test]
+#[should_panic(expected = "the number of significant digits must be in 1..=39")]
+fn notation_zero_digits() {
+    let _ = Scientific::new(1_u8).significant_digits(0);
+}
+
+// ---------------------------------------------------------------------------------------------
+// Humanization with unit prefixes
+
+/// Reference implementation of the humanization, with a single division in fixed point. Returns
+/// `None` when the fixed-point magnitude overflows.
+fn humanize_reference(negative: bool, magnitude: u128, iec: bool, digits: usize, rounding: Rounding) -> Option<String> {
+    let base: u128 = if iec { 1024 } else { 1000 };
+    let prefixes = if iec { ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi"] } else { ["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"] };
+    let scale = |index: u32| -> Option<u128> {
+        let digits = if index == 0 { 0 } else { digits };
+        let pow = 10_u128.pow(u32::try_from(digits).unwrap());
+        let factor = base.pow(index);
+        let fixed = magnitude.checked_mul(pow)?;
+        let (quotient, remainder) = (fixed / factor, fixed % factor);
+        let up = match rounding {
+            Rounding::HalfEven => 2 * remainder > factor || (2 * remainder == factor && quotient % 2 == 1),
+            Rounding::HalfUp => 2 * remainder >= factor,
+            Rounding::Floor => negative && remainder > 0,
+            Rounding::Ceil => !negative && remainder > 0,
+            Rounding::TowardZero => false,
+            Rounding::AwayFromZero => remainder > 0,
+        };
+        Some(quotient + u128::from(up))
+    };
+    let mut index = (0..=10).rev().find(|&i| base.pow(i) <= magnitude).unwrap_or(0);
+    let mut scaled = scale(index)?;
+    let pow = 10_u128.pow(u32::try_from(digits).unwrap());
+    if index > 0 && index < 10 && scaled >= base * pow {
+        index += 1;
+        scaled = scale(index)?;
+    }
+    let sign = if negative { "-" } else { "" };
+    let prefix = prefixes[index as usize];
+    Some(if index == 0 || digits == 0 {
+        format!("{sign}{scaled}{prefix}")
+    } else {
+        format!("{sign}{}.{:0digits$}{prefix}", scaled / pow, scaled % pow)
+    })
+}
+
+/// Compares the humanization of a value with the reference implementation, for a random number
+/// of fractional digits
+macro_rules! check_humanize {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        #[allow(unused_comparisons, clippy::cast_lossless)]
+        let (negative, magnitude) = (value < 0, value.abs_diff(0) as u128);
+        #[allow(clippy::cast_possible_truncation)]
+        let digits = ($rng.next_u64() % 8) as usize;
+        for rounding in ROUNDINGS {
+            for iec in [false, true] {
+                if let Some(expected) = humanize_reference(negative, magnitude, iec, digits, rounding) {
+                    let humanize = if iec { Humanize::iec(value) } else { Humanize::si(value) };
+                    let result = humanize.fraction_digits(digits).rounding(rounding).to_string();
+                    assert_eq!(result, expected, "Humanize({value}, {digits}, {rounding:?}, iec={iec})");
+                }
+            }
+        }
+    }}
+}
+
+test_samples!(humanize, check_humanize, 16, 2_000, 0x9E3779B97F4A7C15);
+
+#[test]
+fn humanize_largest_prefixes() {
+    assert_eq!(Humanize::si(u128::MAX).to_string(), "340282366.92Q");
+    assert_eq!(Humanize::si(i128::MIN).fraction_digits(20).to_string(), "-170141183.46046923173168730372Q");
+    assert_eq!(Humanize::iec(1_u128 << 100).to_string(), "1.00Qi");
+    assert_eq!(Humanize::iec((1_u128 << 100) - 1).fraction_digits(20).rounding(Rounding::TowardZero).to_string(), "1023.99999999999999999999Ri");
+    assert_eq!(Humanize::iec((1_u128 << 100) - 1).fraction_digits(20).to_string(), "1.00000000000000000000Qi");
+    assert_eq!(Humanize::si(10_u128.pow(30) - 1).fraction_digits(3).rounding(Rounding::Ceil).to_string(), "1.000Q");
+    assert_eq!(format!("{:>8}", Humanize::iec(1_u32 << 20)), "  1.00Mi");
+}
+
+#[test]
+#[should_panic(expected = "the number of fractional digits must be in 0..=20")]
+fn humanize_too_many_digits() {
+    let _ = Humanize::si(1_u8).fraction_digits(21);
+}
+
+// ---------------------------------------------------------------------------------------------
+// Durations
+
+#[test]
+fn duration_log() {
+    assert_eq!(Duration::MAX_LOG2, Duration::MAX.as_nanos().ilog2() as usize);
+    assert_eq!(Duration::MAX_LOG10, 28);
+    assert_eq!(Duration::MAX_DECIMAL_DIGITS, format!("{}", Duration::MAX.as_nanos()).len());
+    assert_eq!(Duration::ZERO.checked_log2(), None);
+    assert_eq!(Duration::ZERO.checked_log10(), None);
+    let mut rng = Rng(0xB5AD4ECEDA1CE2A9);
+    for _ in 0..10_000 {
+        let shift = rng.next_u64() % 64;
+        let duration = Duration::new(rng.next_u64() >> shift, u32::try_from(rng.next_u64() % 1_000_000_000).unwrap());
+        let nanos = duration.as_nanos();
+        assert_eq!(duration.log2(), nanos.ilog2() as usize, "log2({duration:?})");
+        assert_eq!(duration.log10(), nanos.ilog10() as usize, "log10({duration:?})");
+        assert_eq!((&duration).checked_log10(), Some(nanos.ilog10() as usize), "checked_log10({duration:?})");
+        assert!(duration.log10_estimate() <= duration.log10() && duration.log10() <= duration.log10_upper_bound());
+    }
+}
+
+/// Reference implementation of the human-readable durations, from the reference scientific notation
+fn duration_reference(nanos: u128, digits: usize, rounding: Rounding) -> String {
+    let scientific = notation_reference(&format!("{nanos}"), digits, rounding, 1);
+    let (significand, exp) = scientific.split_once('e').unwrap();
+    let exp: usize = exp.parse().unwrap();
+    let mut decimal = significand.replace('.', "");
+    let unit = (exp / 3).min(3);
+    let int_len = exp - 3 * unit + 1;
+    let fraction = digits.saturating_sub(int_len).min(3 * unit);
+    while decimal.len() < int_len + fraction {
+        decimal.push('0');
+    }
+    decimal.truncate(int_len + fraction);
+    if fraction > 0 {
+        decimal.insert(int_len, '.');
+    }
+    format!("{decimal}{}", ["ns", "µs", "ms", "s"][unit])
+}
+
+#[test]
+fn human_duration() {
+    let mut rng = Rng(0x6A09E667F3BCC908);
+    for _ in 0..5_000 {
+        let shift = rng.next_u64() % 64;
+        let duration = Duration::new(rng.next_u64() >> shift, u32::try_from(rng.next_u64() % 1_000_000_000).unwrap());
+        let duration = if shift % 2 == 0 { duration } else { Duration::from_nanos(rng.next_u64() >> shift) };
+        #[allow(clippy::cast_possible_truncation)]
+        let digits = 1 + (rng.next_u64() % 30) as usize;
+        for rounding in ROUNDINGS {
+            let human = HumanDuration::new(duration).significant_digits(digits).rounding(rounding).to_string();
+            assert_eq!(human, duration_reference(duration.as_nanos(), digits, rounding), "HumanDuration({duration:?}, {digits}, {rounding:?})");
+        }
+    }
+    assert_eq!(HumanDuration::new(Duration::ZERO).to_string(), "0ns");
+    assert_eq!(HumanDuration::new(Duration::from_nanos(999_500)).rounding(Rounding::HalfUp).to_string(), "1.00ms");
+    assert_eq!(HumanDuration::new(Duration::from_nanos(999_500)).rounding(Rounding::TowardZero).to_string(), "999µs");
+    assert_eq!(HumanDuration::new(Duration::MAX).significant_digits(1).to_string(), "20000000000000000000s");
+    assert_eq!(HumanDuration::new(Duration::MAX).significant_digits(30).to_string(), "18446744073709551615.999999999s");
+}
+
+// ---------------------------------------------------------------------------------------------
+// Aggregation over iterators
+
+/// Tests of the aggregations over random columns, compared with the output of `format!`
+macro_rules! test_log_iterator {
+    ($Name: ident, $SelfT: ty) => {
+        #[test]
+        fn $Name() {
+            let mut rng = Rng(0x3C6EF372FE94F82B);
+            for size in 0..100 {
+                let column: Vec<$SelfT> = (0..size).map(|_| {
+                    let shift = rng.next_u64() % u64::from(<$SelfT>::BITS);
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                    let value = (rng.next_u128() as $SelfT) >> shift;
+                    value
+                }).collect();
+                let formatted: Vec<String> = column.iter().map(ToString::to_string).collect();
+                let expected_width = formatted.iter().map(String::len).max().unwrap_or(0);
+                let expected_total: usize = formatted.iter().map(String::len).sum();
+                #[allow(unused_comparisons)]
+                let expected_log2 = column.iter().filter(|&&v| v > 0).map(|&v| v.ilog2() as usize).max();
+                assert_eq!(column.iter().max_decimal_width(), expected_width, "max_decimal_width({column:?})");
+                assert_eq!(column.iter().copied().max_decimal_width(), expected_width, "max_decimal_width({column:?})");
+                assert_eq!(column.iter().total_decimal_len(), expected_total, "total_decimal_len({column:?})");
+                assert_eq!(column.clone().into_iter().total_decimal_len(), expected_total, "total_decimal_len({column:?})");
+                assert_eq!(column.iter().max_log2(), expected_log2, "max_log2({column:?})");
+            }
+            let extremes = [<$SelfT>::MIN, 0, <$SelfT>::MAX];
+            assert_eq!(extremes.iter().max_decimal_width(), <$SelfT>::MAX_DECIMAL_LEN);
+            assert_eq!(extremes.iter().max_log2(), Some(<$SelfT>::MAX_LOG2));
+            assert_eq!([0 as $SelfT; 3].iter().max_log2(), None);
+            assert_eq!([0 as $SelfT; 3].iter().total_decimal_len(), 3);
+        }
+    }
+}
+
+test_log_iterator!(log_iterator_u8, u8);
+test_log_iterator!(log_iterator_i8, i8);
+test_log_iterator!(log_iterator_u16, u16);
+test_log_iterator!(log_iterator_i16, i16);
+test_log_iterator!(log_iterator_u32, u32);
+test_log_iterator!(log_iterator_i32, i32);
+test_log_iterator!(log_iterator_u64, u64);
+test_log_iterator!(log_iterator_i64, i64);
+test_log_iterator!(log_iterator_u128, u128);
+test_log_iterator!(log_iterator_i128, i128);
+test_log_iterator!(log_iterator_usize, usize);
+test_log_iterator!(log_iterator_isize, isize);
+
+// ---------------------------------------------------------------------------------------------
+// Leading digits
+
+/// Compares the digit access of a value with the output of `format!`
+macro_rules! check_leading_digits {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        let formatted = format!("{}", value.abs_diff(0));
+        let digits: Vec<u8> = formatted.bytes().map(|b| b - b'0').collect();
+        assert_eq!(value.leading_digit(), digits[0], "leading_digit({value})");
+        for n in 0..=digits.len() + 1 {
+            let expected = if n == 0 { 0 } else { formatted[..n.min(digits.len())].parse().unwrap() };
+            assert_eq!(value.leading_digits(n), expected, "leading_digits({value}, {n})");
+        }
+        for index in 0..=digits.len() {
+            assert_eq!(value.nth_decimal_digit(index), digits.get(index).copied(), "nth_decimal_digit({value}, {index})");
+        }
+        assert!(value.decimal_digits().eq(digits.iter().copied()), "decimal_digits({value})");
+        assert!((&value).decimal_digits().rev().eq(digits.iter().rev().copied()), "decimal_digits({value}).rev()");
+        assert_eq!(value.decimal_digits().len(), digits.len(), "decimal_digits({value}).len()");
+        // alternate ends
+        let mut iter = value.decimal_digits();
+        let (mut front, mut back) = (0, digits.len());
+        while front < back {
+            assert_eq!(iter.next(), Some(digits[front]), "decimal_digits({value}).next()");
+            front += 1;
+            if front < back {
+                back -= 1;
+                assert_eq!(iter.next_back(), Some(digits[back]), "decimal_digits({value}).next_back()");
+            }
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }}
+}
+
+test_samples!(leading_digits, check_leading_digits, 16, 5_000, 0xBB67AE8584CAA73B);
+
+// ---------------------------------------------------------------------------------------------
+// Significant figures
+
+/// Reference implementation of the rounding to `n` significant figures, which rounds the digits in
+/// the output of `format!` with the reference scientific notation
+fn sig_figs_reference(formatted: &str, n: usize, rounding: Rounding) -> String {
+    let len = formatted.trim_start_matches('-').len();
+    if len <= n {
+        return formatted.to_string();
+    }
+    let scientific = notation_reference(formatted, n, rounding, 1);
+    let (significand, exp) = scientific.split_once('e').unwrap();
+    let exp: usize = exp.parse().unwrap();
+    format!("{}{}", significand.replace('.', ""), "0".repeat(exp + 1 - n))
+}
+
+/// Compares the rounding to significant figures of a value with the reference implementation, for
+/// the smallest and largest numbers of figures and a random one
+macro_rules! check_sig_figs {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        let formatted = format!("{value}");
+        #[allow(clippy::cast_possible_truncation)]
+        let random_n = 1 + ($rng.next_u64() % (<$SelfT>::MAX_DECIMAL_DIGITS as u64 + 1)) as usize;
+        for n in [1, 2, random_n, <$SelfT>::MAX_DECIMAL_DIGITS, <$SelfT>::MAX_DECIMAL_DIGITS + 1] {
+            for rounding in ROUNDINGS {
+                let expected = sig_figs_reference(&formatted, n, rounding).parse::<$SelfT>().ok();
+                assert_eq!(value.round_sig_figs(n, rounding), expected, "round_sig_figs({value}, {n}, {rounding:?})");
+            }
+            let expected = sig_figs_reference(&formatted, n, Rounding::TowardZero).parse::<$SelfT>().unwrap();
+            assert_eq!(value.truncate_sig_figs(n), expected, "truncate_sig_figs({value}, {n})");
+        }
+        assert_eq!((&value).truncate_sig_figs(1), value.truncate_sig_figs(1), "truncate_sig_figs(&{value}, 1)");
+        assert_eq!(Box::new(value).round_sig_figs(2, Rounding::Ceil), value.round_sig_figs(2, Rounding::Ceil), "round_sig_figs(Box({value}), 2, Ceil)");
+    }}
+}
+
+test_samples!(sig_figs, check_sig_figs, 8, 2_000, 0xA54FF53A5F1D36F1);
+
+#[test]
+fn sig_figs_overflow() {
+    assert_eq!(u128::MAX.round_sig_figs(1, Rounding::Ceil), None);
+    assert_eq!(u128::MAX.round_sig_figs(1, Rounding::Floor), Some(300000000000000000000000000000000000000));
+    assert_eq!(i128::MIN.round_sig_figs(2, Rounding::Floor), None);
+    assert_eq!(i128::MIN.round_sig_figs(2, Rounding::Ceil), Some(-170000000000000000000000000000000000000));
+    assert_eq!(u8::MAX.round_sig_figs(2, Rounding::HalfUp), None);
+    assert_eq!(0_i32.round_sig_figs(1, Rounding::AwayFromZero), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "the number of significant figures must be positive")]
+fn sig_figs_zero() {
+    let _ = 10_u32.truncate_sig_figs(0);
+}
+
+// ---------------------------------------------------------------------------------------------
+// Logarithms rounded to the nearest integer
+
+/// 256-bit unsigned integer, in 64-bit limbs from the least significant one, to compare squares
+#[derive(Clone, Copy)]
+struct U256([u64; 4]);
+
+impl U256 {
+    fn from_u128(value: u128) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        U256([value as u64, (value >> 64) as u64, 0, 0])
+    }
+
+    fn mul_u128(self, rhs: u128) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let rhs = [rhs as u64, (rhs >> 64) as u64];
+        let mut result = [0_u64; 4];
+        for (i, &a) in self.0.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, &b) in rhs.iter().enumerate() {
+                if i + j < 4 {
+                    let sum = u128::from(a) * u128::from(b) + u128::from(result[i + j]) + carry;
+                    #[allow(clippy::cast_possible_truncation)]
+                    { result[i + j] = sum as u64; }
+                    carry = sum >> 64;
+                }
+            }
+            if i + 2 < 4 {
+                #[allow(clippy::cast_possible_truncation)]
+                { result[i + 2] = carry as u64; }
+            }
+        }
+        U256(result)
+    }
+
+    fn square(value: u128) -> Self {
+        U256::from_u128(value).mul_u128(value)
+    }
+
+    fn pow(base: u128, exp: usize) -> Self {
+        (0..exp).fold(U256::from_u128(1), |acc, _| acc.mul_u128(base))
+    }
+
+    fn cmp_value(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+
+    /// Returns the number of significant bits
+    fn bit_len(&self) -> usize {
+        self.0.iter().rposition(|&limb| limb != 0).map_or(0, |i| 64 * i + 64 - self.0[i].leading_zeros() as usize)
+    }
+
+    /// Returns the number of decimal digits
+    fn decimal_len(&self) -> usize {
+        let mut value = self.0;
+        let mut len = 0;
+        loop {
+            let mut remainder = 0_u128;
+            for limb in value.iter_mut().rev() {
+                let current = (remainder << 64) | u128::from(*limb);
+                #[allow(clippy::cast_possible_truncation)]
+                { *limb = (current / 10) as u64; }
+                remainder = current % 10;
+            }
+            len += 1;
+            if value.iter().all(|&limb| limb == 0) {
+                return len;
+            }
+        }
+    }
+}
+
+/// Reference rounded logarithm: `log + 1` if `value² > base^(2·log + 1)`
+fn round_log_reference(value: u128, base: u128) -> usize {
+    let log = if base == 2 { value.ilog2() } else { value.ilog10() } as usize;
+    log + usize::from(U256::square(value).cmp_value(&U256::pow(base, 2 * log + 1)).is_gt())
+}
+
+#[test]
+fn round_log_tables() {
+    for (k, &threshold) in crate::ROUND_LOG10_U128_TABLE.iter().enumerate() {
+        let p = U256::pow(10, 2 * k + 1);
+        assert!(U256::square(threshold).cmp_value(&p).is_lt(), "ROUND_LOG10_U128_TABLE[{k}] too large");
+        assert!(U256::square(threshold + 1).cmp_value(&p).is_gt(), "ROUND_LOG10_U128_TABLE[{k}] too small");
+        if let Some(&threshold64) = crate::ROUND_LOG10_U64_TABLE.get(k) {
+            assert_eq!(u128::from(threshold64), threshold.min(u128::from(u64::MAX)), "ROUND_LOG10_U64_TABLE[{k}]");
+        }
+    }
+    let threshold = crate::ROUND_LOG2_U128_THRESHOLD;
+    assert!(U256::square(threshold).cmp_value(&U256::pow(2, 255)).is_lt());
+    assert!(U256::square(threshold + 1).cmp_value(&U256::pow(2, 255)).is_gt());
+    let threshold = u128::from(crate::ROUND_LOG2_U64_THRESHOLD);
+    assert!(threshold * threshold < 1 << 127 && (threshold + 1) * (threshold + 1) > 1 << 127);
+}
+
+/// Compares the rounded logarithms of a value and of its references with the reference implementation
+macro_rules! check_round_log {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        #[allow(unused_comparisons)]
+        if value <= 0 {
+            assert_eq!(value.checked_round_log2(), None, "checked_round_log2({value})");
+            assert_eq!(value.checked_round_log10(), None, "checked_round_log10({value})");
+            assert_eq!(value.nearest_power_of_ten(), None, "nearest_power_of_ten({value})");
+        } else {
+            #[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+            let wide = value as u128;
+            let expected2 = round_log_reference(wide, 2);
+            let expected10 = round_log_reference(wide, 10);
+            assert_eq!(value.round_log2(), expected2, "round_log2({value})");
+            assert_eq!(value.checked_round_log2(), Some(expected2), "checked_round_log2({value})");
+            assert_eq!(value.round_log10(), expected10, "round_log10({value})");
+            assert_eq!(value.checked_round_log10(), Some(expected10), "checked_round_log10({value})");
+            let expected = 10_u128.checked_pow(u32::try_from(expected10).unwrap()).and_then(|p| <$SelfT>::try_from(p).ok());
+            assert_eq!(value.nearest_power_of_ten(), expected, "nearest_power_of_ten({value})");
+            assert_eq!((&value).round_log2(), expected2, "round_log2(&{value})");
+            assert_eq!((&mut { value }).round_log10(), expected10, "round_log10(&mut {value})");
+            assert_eq!(Box::new(value).nearest_power_of_ten(), expected, "nearest_power_of_ten(Box({value}))");
+        }
+    }}
+}
+
+/// Values around the thresholds of the rounded logarithms
+macro_rules! round_log_thresholds {
+    ($SelfT: ty) => {
+        crate::ROUND_LOG10_U128_TABLE.into_iter()
+            .chain((0..<$SelfT>::BITS - 1).map(|log| crate::ROUND_LOG2_U128_THRESHOLD >> (127 - log)))
+            .flat_map(|threshold| [threshold.saturating_sub(1), threshold, threshold.saturating_add(1)])
+            .filter_map(|value| <$SelfT>::try_from(value).ok())
+    }
+}
+
+test_samples!(round_log, check_round_log, 16, 5_000, 0x510E527FADE682D1, round_log_thresholds);
+
+// ---------------------------------------------------------------------------------------------
+// Rescaling
+
+/// Returns `true` if the kept digits must be incremented, from the parity of the last kept digit
+/// and the dropped digits
+fn round_up_reference(odd: bool, dropped: &[u8], negative: bool, rounding: Rounding) -> bool {
+    let nonzero = dropped.iter().any(|&d| d != b'0');
+    let half = dropped.first().is_some_and(|&d| d >= b'5');
+    let tie = dropped.first() == Some(&b'5') && dropped[1..].iter().all(|&d| d == b'0');
+    match rounding {
+        Rounding::HalfEven => half && (!tie || odd),
+        Rounding::HalfUp => half,
+        Rounding::Floor => negative && nonzero,
+        Rounding::Ceil => !negative && nonzero,
+        Rounding::TowardZero => false,
+        Rounding::AwayFromZero => nonzero,
+    }
+}
+
+/// Reference implementation of the rescaling, on the output of `format!`
+fn rescale_reference(formatted: &str, from_scale: usize, to_scale: usize, rounding: Rounding) -> String {
+    let negative = formatted.starts_with('-');
+    let digits = formatted.trim_start_matches('-');
+    let sign = if negative { "-" } else { "" };
+    if to_scale >= from_scale {
+        return format!("{sign}{digits}{}", "0".repeat(to_scale - from_scale));
+    }
+    let k = from_scale - to_scale;
+    let padded = format!("{}{digits}", "0".repeat(k.saturating_sub(digits.len())));
+    let (kept, dropped) = padded.split_at(padded.len() - k);
+    let kept: u128 = if kept.is_empty() { 0 } else { kept.parse().unwrap() };
+    let up = round_up_reference(kept % 2 == 1, dropped.as_bytes(), negative, rounding);
+    format!("{sign}{}", kept + u128::from(up))
+}
+
+/// Compares the rescaling of a value with the reference implementation, for a few scales and a
+/// random pair of scales
+macro_rules! check_rescale {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        const SCALES: [(usize, usize); 8] = [(0, 0), (2, 8), (8, 2), (0, 1), (1, 0), (3, 5), (0, 45), (45, 0)];
+        let value: $SelfT = $value;
+        let formatted = format!("{value}");
+        #[allow(clippy::cast_possible_truncation)]
+        let random_scales = (($rng.next_u64() % 42) as usize, ($rng.next_u64() % 42) as usize);
+        for (from_scale, to_scale) in SCALES.into_iter().chain([random_scales]) {
+            for rounding in ROUNDINGS {
+                let expected = rescale_reference(&formatted, from_scale, to_scale, rounding).parse::<$SelfT>().ok();
+                // a negative value rounded to "-0" is parsed as 0
+                assert_eq!(value.rescale(from_scale, to_scale, rounding), expected, "rescale({value}, {from_scale}, {to_scale}, {rounding:?})");
+            }
+        }
+        let (from_scale, to_scale) = random_scales;
+        let expected = value.rescale(from_scale, to_scale, Rounding::HalfEven);
+        assert_eq!((&value).rescale(from_scale, to_scale, Rounding::HalfEven), expected, "rescale(&{value}, {from_scale}, {to_scale})");
+        assert_eq!(Box::new(value).rescale(from_scale, to_scale, Rounding::HalfEven), expected, "rescale(Box({value}), {from_scale}, {to_scale})");
+    }}
+}
+
+/// Values whose product by 10 is around the maximum value
+macro_rules! rescale_limits {
+    ($SelfT: ty) => { [<$SelfT>::MAX / 10, <$SelfT>::MAX / 10 + 1, <$SelfT>::MIN / 10, (<$SelfT>::MIN / 10).wrapping_sub(1)] }
+}
+
+test_samples!(rescale, check_rescale, 8, 5_000, 0x9B05688C2B3E6C1F, rescale_limits);
+
+#[test]
+fn rescale_limits() {
+    // the product has as many digits as the limit: exact comparison
+    assert_eq!(12_i8.rescale(0, 1, Rounding::HalfEven), Some(120));
+    assert_eq!(13_i8.rescale(0, 1, Rounding::HalfEven), None);
+    assert_eq!((-12_i8).rescale(0, 1, Rounding::HalfEven), Some(-120));
+    assert_eq!((-128_i16).rescale(0, 2, Rounding::HalfEven), Some(-12800));
+    assert_eq!(i128::MIN.rescale(1, 0, Rounding::Floor), Some(-17014118346046923173168730371588410573));
+    assert_eq!((i128::MIN / 10).rescale(0, 1, Rounding::HalfEven), Some(-170141183460469231731687303715884105720));
+    assert_eq!(u128::MAX.rescale(0, 1, Rounding::HalfEven), None);
+    assert_eq!(i64::MIN.rescale(60, 0, Rounding::Floor), Some(-1));
+    assert_eq!(i64::MIN.rescale(60, 0, Rounding::HalfUp), Some(0));
+}
+
+// ---------------------------------------------------------------------------------------------
+// Multiplications and divisions by powers of ten
+
+/// Compares the decimal shifts of a value with `/` and `%`, for all the exponents up to beyond the
+/// number of digits of the type
+macro_rules! check_decimal_shift {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        for k in (0..=<$SelfT>::MAX_DECIMAL_DIGITS + 2).chain([100, usize::MAX]) {
+            match crate::pow10(k).and_then(|pow| <$SelfT>::try_from(pow).ok()) {
+                Some(pow) => {
+                    assert_eq!(value.div_pow10(k), value / pow, "div_pow10({value}, {k})");
+                    assert_eq!(value.divrem_pow10(k), (value / pow, value % pow), "divrem_pow10({value}, {k})");
+                    assert_eq!(value.checked_mul_pow10(k), value.checked_mul(pow), "checked_mul_pow10({value}, {k})");
+                }
+                None => {
+                    assert_eq!(value.div_pow10(k), 0, "div_pow10({value}, {k})");
+                    assert_eq!(value.divrem_pow10(k), (0, value), "divrem_pow10({value}, {k})");
+                    let expected = if value == 0 { Some(0) } else { None };
+                    assert_eq!(value.checked_mul_pow10(k), expected, "checked_mul_pow10({value}, {k})");
+                }
+            }
+        }
+        assert_eq!((&value).divrem_pow10(1), value.divrem_pow10(1), "divrem_pow10(&{value}, 1)");
+        assert_eq!(Box::new(value).checked_mul_pow10(1), value.checked_mul_pow10(1), "checked_mul_pow10(Box({value}), 1)");
+    }}
+}
+
+/// Values around the multiples of the powers of ten
+macro_rules! pow10_multiples {
+    ($SelfT: ty) => {
+        (1..<$SelfT>::MAX_DECIMAL_DIGITS).flat_map(|k| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            let pow = crate::pow10(k).unwrap() as $SelfT;
+            [pow, <$SelfT>::MAX / pow * pow].into_iter().flat_map(|multiple| [multiple - 1, multiple, multiple.saturating_add(1)])
+        })
+    }
+}
+
+test_samples!(decimal_shift, check_decimal_shift, 16, 20_000, 0x5DEECE66D1B873A3, pow10_multiples);
+
+// ---------------------------------------------------------------------------------------------
+// Logarithms of products and powers
+
+/// Bounds of the target types of `fits_mul`, as the magnitudes of the minimum and maximum values
+const FITS_BOUNDS: [(u128, u128); 6] = [
+    (0, u8::MAX as u128), (128, i8::MAX as u128), (0, u32::MAX as u128),
+    (1 << 63, i64::MAX as u128), (0, u128::MAX), (1 << 127, i128::MAX as u128),
+];
+
+/// Compares the logarithms of the products of a value, by all the values for 8-bit types or else
+/// by the extreme values and a random one, and of its powers with the 256-bit reference
+macro_rules! check_log_product {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let a: $SelfT = $value;
+        let shift = $rng.next_u64() % u64::from(<$SelfT>::BITS);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let random = ($rng.next_u128() as $SelfT) >> shift;
+        let others: Vec<$SelfT> = if <$SelfT>::BITS == 8 {
+            (<$SelfT>::MIN..=<$SelfT>::MAX).collect()
+        } else {
+            vec![<$SelfT>::MIN, <$SelfT>::MIN + 1, 0, 1, 2, 10, <$SelfT>::MAX - 1, <$SelfT>::MAX, random]
+        };
+        for b in others {
+            #[allow(clippy::cast_lossless)]
+            let product = U256::from_u128(a.abs_diff(0) as u128).mul_u128(b.abs_diff(0) as u128);
+            let is_zero = product.bit_len() == 0;
+            #[allow(unused_comparisons)]
+            let negative = (a < 0) != (b < 0) && !is_zero;
+            let expected_log2 = (!is_zero).then(|| product.bit_len() - 1);
+            let expected_log10 = (!is_zero).then(|| product.decimal_len() - 1);
+            assert_eq!(a.log2_mul(b), expected_log2, "log2_mul({a}, {b})");
+            assert_eq!(a.log10_mul(b), expected_log10, "log10_mul({a}, {b})");
+            let fits = [a.fits_mul::<u8>(b), a.fits_mul::<i8>(b), a.fits_mul::<u32>(b), a.fits_mul::<i64>(b), a.fits_mul::<u128>(b), a.fits_mul::<i128>(b)];
+            for (fit, (min, max)) in fits.into_iter().zip(FITS_BOUNDS) {
+                let bound = U256::from_u128(if negative { min } else { max });
+                assert_eq!(fit, product.cmp_value(&bound).is_le(), "fits_mul({a}, {b}) in [-{min}, {max}]");
+            }
+            assert_eq!(a.fits_mul::<$SelfT>(b), a.checked_mul(b).is_some(), "fits_mul({a}, {b})");
+        }
+        for n in [0_u32, 1, 2, 3, 7] {
+            #[allow(clippy::cast_lossless)]
+            let magnitude = a.abs_diff(0) as u128;
+            // the reference power is only computed when it fits in 256 bits
+            if n == 0 || (u128::BITS - magnitude.leading_zeros()) * n < 256 {
+                let expected = if n == 0 { Some(0) } else { U256::pow(magnitude, n as usize).bit_len().checked_sub(1) };
+                assert_eq!(a.log2_pow(n), expected, "log2_pow({a}, {n})");
+            }
+        }
+        assert_eq!((&a).log10_mul(&random), a.log10_mul(random), "log10_mul(&{a}, &{random})");
+        assert_eq!(Box::new(a).log2_mul(Box::new(random)), a.log2_mul(random), "log2_mul(Box({a}), Box({random}))");
+        assert_eq!((&mut a.clone()).log2_pow(5), a.log2_pow(5), "log2_pow(&mut {a}, 5)");
+    }}
+}
+
+test_samples!(log_product, check_log_product, 8, 5_000, 0x2545F4914F6CDD1D);
+
+/// Reference base 2 logarithm of `a^n`, computed with big integers in 64-bit limbs
+fn log2_pow_reference(a: u128, n: u32) -> usize {
+    fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut product = vec![0_u64; a.len() + b.len()];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, &y) in b.iter().enumerate() {
+                let sum = u128::from(x) * u128::from(y) + u128::from(product[i + j]) + carry;
+                #[allow(clippy::cast_possible_truncation)]
+                { product[i + j] = sum as u64; }
+                carry = sum >> 64;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            { product[i + b.len()] = carry as u64; }
+        }
+        while product.len() > 1 && product.last() == Some(&0) {
+            product.pop();
+        }
+        product
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let base = mul_limbs(&[a as u64, (a >> 64) as u64], &[1]);
+    let power = (0..n).fold(vec![1_u64], |power, _| mul_limbs(&power, &base));
+    64 * (power.len() - 1) + power.last().map_or(0, |&limb| limb.ilog2() as usize)
+}
+
+#[test]
+fn log2_pow_large() {
+    // the bounds of the powers against the exact computation
+    let mut rng = Rng(0x6C8E9CF570932BD5);
+    for _ in 0..500 {
+        let shift = rng.next_u64() % 128;
+        let a = (rng.next_u128() >> shift) | 1;
+        #[allow(clippy::cast_possible_truncation)]
+        let n = (rng.next_u64() % 64) as u32 + 1;
+        assert_eq!(a.log2_pow(n), Some(log2_pow_reference(a, n)), "log2_pow({a}, {n})");
+        assert_eq!((a << a.leading_zeros()).log2_pow(n), Some(log2_pow_reference(a, n) + n as usize * a.leading_zeros() as usize));
+        assert_eq!(crate::product::log2_pow_wide(a, n), Some(log2_pow_reference(a, n)), "log2_pow_wide({a}, {n})");
+    }
+    for a in 3..200_u128 {
+        let a = a | 1;
+        assert_eq!(log2_pow_reference(a, 5), U256::pow(a, 5).bit_len() - 1);
+    }
+    // n·log2(a) is within 2^-109 of an integer, so the 128-bit bounds don't decide
+    assert_eq!(170139721362468192984556712071200037721_u128.log2_pow(1 << 20), Some(133169138));
+    // floor(n·log2(10)) for large n
+    assert_eq!(10_u64.log2_pow(100_000), Some(332192));
+    assert_eq!(u128::MAX.log2_pow(u32::MAX), usize::try_from(128 * u64::from(u32::MAX) - 1).ok());
+    assert_eq!(2_u8.log2_pow(u32::MAX), usize::try_from(u32::MAX).ok());
+    assert_eq!((-1_i8).log2_pow(u32::MAX), Some(0));
+}
+
+// ---------------------------------------------------------------------------------------------
+// Trailing zeros and valuations
+
+/// Reference multiplicity of `base` in `value`, by repeated divisions
+fn valuation_reference(mut value: u128, base: u128) -> Option<usize> {
+    if value == 0 || base < 2 {
+        return None;
+    }
+    let mut count = 0;
+    while value % base == 0 {
+        value /= base;
+        count += 1;
+    }
+    Some(count)
+}
+
+/// Compares the trailing zeros and the valuations of a value with the references, for a few bases
+/// including the powers of ten and a random base
+macro_rules! check_valuation {
+    ($SelfT: ty, $value: ident, $rng: ident) => {{
+        let value: $SelfT = $value;
+        #[allow(clippy::cast_lossless)]
+        let magnitude = value.abs_diff(0) as u128;
+        let zeros = valuation_reference(magnitude, 10).unwrap_or(0);
+        assert_eq!(value.trailing_decimal_zeros(), zeros, "trailing_decimal_zeros({value})");
+        let mut stripped = value;
+        for _ in 0..zeros {
+            stripped /= 10;
+        }
+        assert_eq!(value.strip_trailing_zeros(), stripped, "strip_trailing_zeros({value})");
+        #[allow(unused_comparisons)]
+        let signed = <$SelfT>::MIN < 0;
+        let mut bases: Vec<$SelfT> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 12, 16, 25, 64, 100, 127, <$SelfT>::MAX];
+        if signed {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            bases.extend([(-1_i8) as $SelfT, (-2_i8) as $SelfT, (-10_i8) as $SelfT, <$SelfT>::MIN]);
+        }
+        bases.extend((3..<$SelfT>::MAX_DECIMAL_DIGITS).filter_map(|k| crate::pow10(k).and_then(|pow| <$SelfT>::try_from(pow).ok())));
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        bases.push(($rng.next_u64() % 1000) as $SelfT);
+        for base in bases {
+            #[allow(clippy::cast_lossless)]
+            let expected = valuation_reference(magnitude, base.abs_diff(0) as u128);
+            assert_eq!(value.valuation(base), expected, "valuation({value}, {base})");
+        }
+        assert_eq!((&value).valuation(&10), value.valuation(10), "valuation(&{value}, &10)");
+        assert_eq!(Box::new(value).strip_trailing_zeros(), stripped, "strip_trailing_zeros(Box({value}))");
+    }}
+}
+
+/// Products of random small factors, so that the values have many trailing zeros, with their
+/// neighbours and their negations
+macro_rules! smooth_values {
+    ($SelfT: ty) => {{
+        let mut rng = Rng(0x3C6EF372FE94F82B);
+        let mut values: Vec<$SelfT> = Vec::new();
+        for _ in 0..20_000 {
+            let mut value: $SelfT = 1;
+            while let Some(product) = value.checked_mul([2, 3, 5, 7, 10][(rng.next_u64() % 5) as usize]) {
+                value = product;
+                if rng.next_u64() % 16 == 0 {
+                    break;
+                }
+            }
+            values.extend([value, value.wrapping_add(1), value.wrapping_neg()]);
+        }
+        values
+    }}
+}
+
+test_samples!(valuation, check_valuation, 16, 5_000, 0x3C6EF372FE94F82B, smooth_values);
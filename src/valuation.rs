@@ -0,0 +1,158 @@
+// Copyright 2022 Redglyph
+//
+// Trailing zeros in base 10 and multiplicity of a factor
+
+use crate::{DecimalShift, Log10};
+use alloc::boxed::Box;
+
+// =============================================================================================
+
+/// Trait that counts the trailing zeros of integers in base 10, and more generally the
+/// multiplicity of a factor, which is the p-adic valuation when the factor is a prime `p`.
+///
+/// Instead of dividing the value by the base in a loop, the multiplicity is found by a binary
+/// search over the exponents, whose upper bound is given by the logarithm of the value: in base 10,
+/// the powers of ten are taken from the tables and divided with [`DecimalShift`], which also gives
+/// the multiplicity of the other powers of ten. For a power-of-two base, the multiplicity is derived
+/// from [`trailing_zeros`](u32::trailing_zeros). For the other bases, the binary search falls back
+/// on the powers of the base computed with [`pow`](u32::pow), up to the [`ilog`](u32::ilog) of the
+/// value, and on the hardware division.
+///
+/// The sign of the value and of the base is ignored.
+///
+/// # Examples
+/// ```
+/// use ilog::Valuation;
+///
+/// assert_eq!(1200_u32.trailing_decimal_zeros(), 2);
+/// assert_eq!((-1200_i32).strip_trailing_zeros(), -12);
+/// assert_eq!(1200_u32.valuation(5), Some(2));    // 1200 = 2^4·3·5^2
+/// assert_eq!(1200_u32.valuation(4), Some(2));
+/// assert_eq!(0_u32.valuation(5), None);
+/// ```
+pub trait Valuation: Sized {
+    /// Type of the values without their trailing zeros, which is the integer type, also for its references.
+    type Output;
+
+    /// Returns the number of trailing zeros of the value in base 10, which is 0 for zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Valuation;
+    /// assert_eq!(10000000000000000000_u64.trailing_decimal_zeros(), 19);
+    /// assert_eq!(i8::MIN.trailing_decimal_zeros(), 0);
+    /// assert_eq!(0_u8.trailing_decimal_zeros(), 0);
+    /// ```
+    fn trailing_decimal_zeros(self) -> usize;
+
+    /// Returns the value without its trailing zeros in base 10, which is the value divided by
+    /// `10^trailing_decimal_zeros`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Valuation;
+    /// assert_eq!(1_250_000_u32.strip_trailing_zeros(), 125);
+    /// assert_eq!((-100_i8).strip_trailing_zeros(), -1);
+    /// assert_eq!(0_u8.strip_trailing_zeros(), 0);
+    /// ```
+    #[must_use]
+    fn strip_trailing_zeros(self) -> Self::Output;
+
+    /// Returns the multiplicity of `base` in the value, which is the largest `k` such that `base^k`
+    /// divides the value, or `None` if the value is zero or if the magnitude of `base` is less
+    /// than 2.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ilog::Valuation;
+    /// assert_eq!(96_u8.valuation(2), Some(5));      // 96 = 2^5·3
+    /// assert_eq!(96_u8.valuation(8), Some(1));
+    /// assert_eq!((-81_i16).valuation(-3), Some(4));
+    /// assert_eq!(u128::MAX.valuation(17), Some(1));
+    /// assert_eq!(10_000_000_u32.valuation(100), Some(3));
+    /// assert_eq!(96_u8.valuation(1), None);
+    /// ```
+    fn valuation(self, base: Self) -> Option<usize>;
+}
+
+// ---------------------------------------------------------------------------------------------
+
+/// Expands `Valuation` trait to references
+macro_rules! forward_ref_valuation {
+    ($SelfT: ty; $( $t:ty ),+) => {$(
+        impl Valuation for $t {
+            type Output = $SelfT;
+
+            #[inline]
+            fn trailing_decimal_zeros(self) -> usize {
+                Valuation::trailing_decimal_zeros(*self)
+            }
+            #[inline]
+            fn strip_trailing_zeros(self) -> $SelfT {
+                Valuation::strip_trailing_zeros(*self)
+            }
+            #[inline]
+            fn valuation(self, base: Self) -> Option<usize> {
+                Valuation::valuation(*self, *base)
+            }
+        }
+    )+}
+}
+
+/// Implements `Valuation` trait for integer type, from its magnitude
+macro_rules! impl_valuation {
+    ($($SelfT: ty),+) => {$(
+        impl Valuation for $SelfT {
+            type Output = $SelfT;
+
+            #[inline]
+            fn trailing_decimal_zeros(self) -> usize {
+                let magnitude = self.abs_diff(0);
+                if magnitude == 0 {
+                    return 0;
+                }
+                // 10^k divides the value only if 2^k divides it and if 10^k <= value
+                let (mut low, mut high) = (0, (magnitude.trailing_zeros() as usize).min(magnitude.log10()));
+                while low < high {
+                    let mid = (low + high).div_ceil(2);
+                    if magnitude.divrem_pow10(mid).1 == 0 { low = mid } else { high = mid - 1 }
+                }
+                low
+            }
+
+            #[inline]
+            fn strip_trailing_zeros(self) -> $SelfT {
+                // the division is exact, so the truncation toward zero doesn't change the value
+                self.div_pow10(self.trailing_decimal_zeros())
+            }
+
+            #[inline]
+            fn valuation(self, base: Self) -> Option<usize> {
+                let magnitude = self.abs_diff(0);
+                let base = base.abs_diff(0);
+                if magnitude == 0 || base < 2 {
+                    return None;
+                }
+                if base.is_power_of_two() {
+                    return Some((magnitude.trailing_zeros() / base.trailing_zeros()) as usize);
+                }
+                // 10^d divides the value as many times as 10 divides it, divided by d
+                let log = base.log10();
+                if base.divrem_pow10(log) == (1, 0) {
+                    return Some(self.trailing_decimal_zeros() / log);
+                }
+                // base^k divides the value only if base^k <= value
+                let (mut low, mut high) = (0, magnitude.ilog(base));
+                while low < high {
+                    let mid = (low + high).div_ceil(2);
+                    if magnitude % base.pow(mid) == 0 { low = mid } else { high = mid - 1 }
+                }
+                Some(low as usize)
+            }
+        }
+
+        forward_ref_valuation!($SelfT; &$SelfT, &mut $SelfT, Box<$SelfT>);
+    )+}
+}
+
+impl_valuation! { u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize }
@@ -3,8 +3,10 @@
 // Integration tests: tests that all the functionalities are accessible and work as expected.
 
 #![cfg(test)]
+// the tests borrow the values on purpose, to check the implementations for the references
+#![allow(clippy::needless_borrows_for_generic_args)]
 
-use ilog::IntLog;
+use ilog::{Checked, IntLog, Log10, Log2, LogPrimitive, LogWith, Panic, Saturate, Wrapping};
 extern crate alloc;
 use alloc::boxed::Box;
 
@@ -65,6 +67,17 @@ macro_rules! intg_log {
             assert_eq!(zero_chk_log10, None, "checked_log10(0)");
             assert_eq!(forbid_chk_log2, None, "checked_log2({})", $Forbidden);
             assert_eq!(forbid_chk_log10, None, "checked_log10({})", $Forbidden);
+
+            // estimates
+            let value2_est = value2.log10_estimate();
+            let value2_upper = value2.log10_upper_bound();
+            let ref_value2_est = (&value2).log10_estimate();
+            let ref_value2_upper = (&value2).log10_upper_bound();
+
+            assert!(value2_est == $Exp10 || value2_est + 1 == $Exp10, "log10_estimate(value2)");
+            assert!(value2_upper == $Exp10 || value2_upper == $Exp10 + 1, "log10_upper_bound(value2)");
+            assert_eq!(ref_value2_est, value2_est, "log10_estimate(ref_value2)");
+            assert_eq!(ref_value2_upper, value2_upper, "log10_upper_bound(ref_value2)");
         }
     }
 }
@@ -211,3 +224,176 @@ fn log_u32_intgx() {
     assert_eq!(box_value1_chk_log2, Some(9 - 1), "refmut_value1.checked_log2()");
     assert_eq!(box_value1_chk_log10, Some(2), "refmut_value1.checked_log10()");
 }
+
+/// Custom type that only supports the base 2 logarithm
+#[derive(Clone, Copy)]
+struct Bits(u32);
+
+impl Log2 for Bits {
+    const MAX_LOG2: usize = 31;
+
+    fn log2(self) -> usize {
+        self.0.log2()
+    }
+
+    fn checked_log2(self) -> Option<usize> {
+        self.0.checked_log2()
+    }
+}
+
+#[test]
+fn log2_only_intg() {
+    fn bit_length(x: impl Log2) -> usize {
+        x.checked_log2().map_or(0, |log| log + 1)
+    }
+    fn digits(x: impl Log10) -> usize {
+        x.checked_log10().map_or(1, |log| log + 1)
+    }
+
+    assert_eq!(bit_length(Bits(0)), 0);
+    assert_eq!(bit_length(Bits(255)), 8);
+    assert_eq!(bit_length(Bits(256)), 9);
+    assert_eq!(bit_length(256_u16), 9);
+    assert_eq!(digits(256_u16), 3);
+    assert_eq!(Bits::MAX_LOG2, u32::MAX_LOG2);
+}
+
+/// Newtypes implementing the logarithm traits with `impl_intlog_newtype!`
+struct UserId(u64);
+#[derive(Clone, Copy)]
+struct Offset(i32);
+
+ilog::impl_intlog_newtype!(UserId(u64), Offset(i32));
+
+#[test]
+fn newtype_intg() {
+    fn digits(x: impl IntLog) -> usize {
+        x.checked_log10().map_or(1, |log| log + 1)
+    }
+
+    let mut id = UserId(123_456);
+    assert_eq!(id.0, 123_456);
+    assert_eq!((&id).log10(), 5);
+    assert_eq!((&id).log2(), 16);
+    assert_eq!((&mut id).checked_log10(), Some(5));
+    assert_eq!((&mut id).checked_log2(), Some(16));
+    assert_eq!(digits(&id), 6);
+    assert_eq!(digits(Box::new(UserId(0))), 1);
+    assert_eq!(UserId(1000).log10_estimate(), 2);
+    assert_eq!(UserId(1000).log10_upper_bound(), 3);
+    assert_eq!(UserId::MAX_LOG2, u64::MAX_LOG2);
+    assert_eq!(<&UserId>::MAX_DECIMAL_LEN, u64::MAX_DECIMAL_LEN);
+
+    assert_eq!(Offset(-5).checked_log10(), None);
+    assert_eq!(Offset(1 << 20).log2(), 20);
+    assert_eq!(<Box<Offset>>::MAX_DECIMAL_LEN, i32::MAX_DECIMAL_LEN);
+    assert_eq!(Offset(-5).log10_with::<Checked>(), None);
+    assert_eq!(Offset(-1).log2_with::<Wrapping>(), 31);
+    assert_eq!((&id).log10_with::<Panic>(), 5);
+}
+
+/// Custom 24-bit integer implementing `LogPrimitive`
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+struct U24(u32);
+
+impl LogPrimitive for U24 {
+    const BITS: u32 = 24;
+
+    fn leading_zeros(self) -> u32 {
+        self.0.leading_zeros() - 8
+    }
+
+    fn from_u128(value: u128) -> Self {
+        U24(u32::try_from(value).unwrap())
+    }
+}
+
+/// Custom 128-bit integer implementing `LogPrimitive`
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+struct W128(u128);
+
+impl LogPrimitive for W128 {
+    const BITS: u32 = 128;
+
+    fn leading_zeros(self) -> u32 {
+        self.0.leading_zeros()
+    }
+
+    fn from_u128(value: u128) -> Self {
+        W128(value)
+    }
+}
+
+#[test]
+fn log_primitive_intg() {
+    fn check(value: u32) {
+        let x = U24(value);
+        assert_eq!(x.checked_log2(), value.checked_log2(), "checked_log2({value})");
+        assert_eq!(x.checked_log10(), value.checked_log10(), "checked_log10({value})");
+        if value > 0 {
+            assert_eq!(x.log2(), value.log2(), "log2({value})");
+            assert_eq!(x.log10(), value.log10(), "log10({value})");
+            let estimate = x.log10_estimate();
+            assert!(estimate == x.log10() || estimate + 1 == x.log10(), "log10_estimate({value})");
+            assert_eq!(x.log10_upper_bound(), estimate + 1, "log10_upper_bound({value})");
+        }
+    }
+
+    for value in (0..1 << 24).step_by(997).chain((0..24).flat_map(|i| [1 << i, (2 << i) - 1])) {
+        check(value);
+    }
+    for value in core::iter::successors(Some(1_u32), |v| Some(v * 10)).take_while(|&v| v < 1 << 24) {
+        check(value - 1);
+        check(value);
+    }
+    assert_eq!(U24::MAX_LOG2, 23);
+    assert_eq!(U24::MAX_LOG10, 7);
+    assert_eq!(U24::MAX_DECIMAL_DIGITS, 8);
+    assert_eq!(U24::MAX_DECIMAL_LEN, 8);
+    assert_eq!(U24(0).log10_with::<Saturate>(), 0);
+    assert_eq!(U24(1000).log10_with::<Checked>(), Some(3));
+
+    for i in 0..128 {
+        for value in [1_u128 << i, (1_u128 << i) | ((1_u128 << i) - 1)] {
+            assert_eq!(W128(value).log2(), value.log2(), "log2({value})");
+            assert_eq!(W128(value).log10(), value.log10(), "log10({value})");
+        }
+    }
+    for value in core::iter::successors(Some(1_u128), |v| v.checked_mul(10)) {
+        assert_eq!(W128(value - 1).checked_log10(), (value - 1).checked_log10(), "checked_log10({})", value - 1);
+        assert_eq!(W128(value).log10(), value.log10(), "log10({value})");
+    }
+    assert_eq!(W128(0).checked_log10(), None);
+    assert_eq!(W128::MAX_LOG10, u128::MAX_LOG10);
+}
+
+#[test]
+fn duration_intlog() {
+    use core::time::Duration;
+
+    fn bits_and_digits(value: impl IntLog + Copy) -> (usize, usize) {
+        (value.log2() + 1, value.log10() + 1)
+    }
+    assert_eq!(bits_and_digits(Duration::from_micros(1)), (10, 4));
+    assert_eq!(bits_and_digits(&Duration::from_secs(1)), (30, 10));
+    assert_eq!(Duration::ZERO.checked_log10(), None);
+    assert_eq!(Duration::ZERO.log10_with::<Checked>(), None);
+    assert_eq!(Duration::ZERO.log2_with::<Wrapping>(), 0);
+    assert_eq!((&Duration::from_millis(1)).log10_with::<Panic>(), 6);
+}
+
+/// Newtype of a `Duration`, which implements `LogWith` like the integers
+#[derive(Clone, Copy)]
+struct Timeout(core::time::Duration);
+
+ilog::impl_intlog_newtype!(Timeout(core::time::Duration));
+
+#[test]
+fn duration_newtype_intg() {
+    use core::time::Duration;
+
+    let timeout = Timeout(Duration::from_micros(10));
+    assert_eq!(timeout.log10(), 4);
+    assert_eq!((&timeout).log2_with::<Checked>(), Some(13));
+    assert_eq!(Timeout(Duration::ZERO).log10_with::<Saturate>(), 0);
+}